@@ -0,0 +1,247 @@
+use crate::util::{check_signature, into_js, returns_result, strip_js_attrs, take_args, JsAttrs, MacroOptions};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, FnArg, ImplItem, ImplItemFn, Item, ItemImpl, LitStr, Result, Type};
+
+pub fn expand_class(item: Item, options: MacroOptions) -> Result<TokenStream> {
+    let (ident, generics) = match &item {
+        Item::Struct(s) => (&s.ident, &s.generics),
+        Item::Enum(e) => (&e.ident, &e.generics),
+        _ => return Err(Error::new_spanned(&item, "#[js_class] can only be used on structs and enums")),
+    };
+    if !generics.params.is_empty() {
+        return Err(Error::new_spanned(generics, "#[js_class] does not support generic types"));
+    }
+    let krate = options.krate();
+    let name = options.or(ident);
+    Ok(quote! {
+        #item
+
+        impl #krate::value::JsClass for #ident {
+            const NAME: &'static str = #name;
+        }
+    })
+}
+
+enum Receiver {
+    None,
+    Ref,
+    Mut,
+}
+
+fn receiver(method: &ImplItemFn) -> Result<Receiver> {
+    match method.sig.inputs.first() {
+        Some(FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_some() => Ok(Receiver::Mut),
+        Some(FnArg::Receiver(r)) if r.reference.is_some() => Ok(Receiver::Ref),
+        Some(FnArg::Receiver(r)) => Err(Error::new_spanned(
+            r,
+            "#[js_methods] cannot export methods taking `self` by value; take `&self` or `&mut self`",
+        )),
+        _ => Ok(Receiver::None),
+    }
+}
+
+fn params(method: &ImplItemFn) -> Vec<&Type> {
+    method
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(arg) => Some(&*arg.ty),
+            FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+struct Accessor<'a> {
+    name: LitStr,
+    get: Option<&'a ImplItemFn>,
+    set: Option<&'a ImplItemFn>,
+}
+
+pub fn expand_methods(mut block: ItemImpl, options: MacroOptions) -> Result<TokenStream> {
+    if let Some((_, path, _)) = &block.trait_ {
+        return Err(Error::new_spanned(path, "#[js_methods] must be used on an inherent impl block"));
+    }
+    if !block.generics.params.is_empty() {
+        return Err(Error::new_spanned(&block.generics, "#[js_methods] does not support generic impl blocks"));
+    }
+
+    let krate = options.krate();
+    let mut constructor: Option<TokenStream> = None;
+    let mut members = Vec::new();
+    let mut accessors: Vec<Accessor<'_>> = Vec::new();
+
+    let methods: Vec<(&ImplItemFn, JsAttrs)> = block
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(method) => Some(method),
+            _ => None,
+        })
+        .map(|method| Ok((method, JsAttrs::parse(&method.attrs, &["constructor", "get", "set", "skip"])?)))
+        .collect::<Result<_>>()?;
+
+    for (method, attrs) in &methods {
+        if attrs.skip.is_some() {
+            continue;
+        }
+        check_signature(&method.sig, "#[js_methods]")?;
+        let ident = &method.sig.ident;
+        let receiver = receiver(method)?;
+        let types = params(method);
+
+        if let Some(span) = attrs.constructor {
+            if !matches!(receiver, Receiver::None) {
+                return Err(Error::new(span, "a #[js(constructor)] must not take `self`"));
+            }
+            if constructor.is_some() {
+                return Err(Error::new(span, "only one method can be the #[js(constructor)]"));
+            }
+            let (glue, args) = take_args(&krate, &types);
+            let call = if returns_result(&method.sig.output) {
+                quote!(<Self>::#ident(#(#args),*))
+            } else {
+                quote!(Ok(<Self>::#ident(#(#args),*)))
+            };
+            constructor = Some(quote! {
+                #glue
+                #call
+            });
+            continue;
+        }
+
+        if attrs.get.is_some() || attrs.set.is_some() {
+            let is_get = attrs.get.is_some();
+            let span = attrs.get.or(attrs.set).unwrap();
+            let name = match &attrs.name {
+                Some(name) => name.clone(),
+                None if is_get => LitStr::new(&ident.to_string(), ident.span()),
+                None => {
+                    let name = ident.to_string();
+                    LitStr::new(name.strip_prefix("set_").unwrap_or(&name), ident.span())
+                }
+            };
+            match (is_get, &receiver, types.len()) {
+                (true, Receiver::Ref, 0) => {}
+                (true, ..) => return Err(Error::new(span, "a #[js(get)] method must take only `&self`")),
+                (false, Receiver::Mut, 1) => {}
+                (false, ..) => {
+                    return Err(Error::new(span, "a #[js(set)] method must take `&mut self` and one value"));
+                }
+            }
+            let index = match accessors.iter().position(|a| a.name.value() == name.value()) {
+                Some(index) => index,
+                None => {
+                    accessors.push(Accessor { name, get: None, set: None });
+                    accessors.len() - 1
+                }
+            };
+            let slot = if is_get { &mut accessors[index].get } else { &mut accessors[index].set };
+            if slot.is_some() {
+                return Err(Error::new(span, "duplicate accessor for this property"));
+            }
+            *slot = Some(method);
+            continue;
+        }
+
+        let name = attrs.name.clone().unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+        let (glue, args) = take_args(&krate, &types);
+        members.push(match receiver {
+            Receiver::Ref | Receiver::Mut => {
+                let register = match receiver {
+                    Receiver::Mut => quote!(method_mut),
+                    _ => quote!(method),
+                };
+                let call = into_js(&krate, quote!(<Self>::#ident(this, #(#args),*)), &method.sig.output);
+                quote! {
+                    class.#register(#name, |guard, this, info| {
+                        #glue
+                        #call
+                    })
+                }
+            }
+            Receiver::None => {
+                let call = into_js(&krate, quote!(<Self>::#ident(#(#args),*)), &method.sig.output);
+                quote! {
+                    class.static_method(#name, |guard, info| {
+                        #glue
+                        #call
+                    })
+                }
+            }
+        });
+    }
+
+    for accessor in &accessors {
+        let name = &accessor.name;
+        let Some(get) = accessor.get else {
+            let set = accessor.set.unwrap();
+            return Err(Error::new_spanned(
+                &set.sig.ident,
+                format!("setter for `{}` has no matching #[js(get)] method", name.value()),
+            ));
+        };
+        let get_ident = &get.sig.ident;
+        let getter = {
+            let call = into_js(&krate, quote!(<Self>::#get_ident(this)), &get.sig.output);
+            quote!(|guard, this| #call)
+        };
+        members.push(match accessor.set {
+            None => quote!(class.getter(#name, #getter)),
+            Some(set) => {
+                let set_ident = &set.sig.ident;
+                let ty = params(set)[0];
+                let call = if returns_result(&set.sig.output) {
+                    quote!(<Self>::#set_ident(this, value)?;)
+                } else {
+                    quote!(<Self>::#set_ident(this, value);)
+                };
+                quote! {
+                    class.accessor(#name, #getter, |guard, this, value| {
+                        let value = <#ty as #krate::value::FromJs>::from_js(guard, &value)?;
+                        #call
+                        Ok(())
+                    })
+                }
+            }
+        });
+    }
+
+    let constructor = constructor.unwrap_or_else(|| {
+        quote! {
+            Err(#krate::type_err(format!(
+                "{} cannot be constructed from script",
+                <Self as #krate::value::JsClass>::NAME
+            )))
+        }
+    });
+
+    for item in &mut block.items {
+        if let ImplItem::Fn(method) = item {
+            strip_js_attrs(&mut method.attrs);
+        }
+    }
+    let self_ty = &block.self_ty;
+
+    Ok(quote! {
+        #block
+
+        impl #krate::value::JsMethods for #self_ty {
+            #[allow(unused_variables)]
+            fn construct<'a>(
+                guard: &'a #krate::Guard<'_>,
+                info: &#krate::value::CallInfo<'a>,
+            ) -> #krate::Result<Self> {
+                #constructor
+            }
+
+            fn members<'g>(
+                class: #krate::value::Class<'g, Self>,
+            ) -> #krate::Result<#krate::value::Class<'g, Self>> {
+                #(let class = #members?;)*
+                Ok(class)
+            }
+        }
+    })
+}
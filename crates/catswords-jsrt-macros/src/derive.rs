@@ -0,0 +1,288 @@
+use crate::util::{krate, JsAttrs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Generics, Ident, LitStr, Result};
+
+// Adds the `'__g` lifetime and bounds every type parameter by the derived trait.
+fn impl_generics(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut out = generics.clone();
+    let params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    out.params.insert(0, parse_quote!('__g));
+    let where_clause = out.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    out
+}
+
+fn js_name(attrs: &[syn::Attribute], ident: &Ident) -> Result<LitStr> {
+    let attrs = JsAttrs::parse(attrs, &[])?;
+    Ok(attrs.name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span())))
+}
+
+// `#[js(crate = "...")]` on the type itself.
+fn container_krate(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = JsAttrs::parse(&input.attrs, &["crate"])?;
+    Ok(krate(attrs.krate.as_ref()))
+}
+
+fn check_data(input: &DeriveInput, trait_name: &str) -> Result<()> {
+    match &input.data {
+        Data::Union(u) => Err(Error::new_spanned(
+            u.union_token,
+            format!("{} cannot be derived for unions", trait_name),
+        )),
+        Data::Enum(e) if e.variants.is_empty() => Err(Error::new_spanned(
+            &input.ident,
+            format!("{} cannot be derived for enums without variants", trait_name),
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Bindings for the fields of a struct or variant when destructured. Numbered
+// so that fields named like the generated locals (`guard`, `value`) still work.
+fn bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.len()).map(|i| format_ident!("field{}", i)).collect()
+}
+
+fn pattern(path: TokenStream, fields: &Fields, names: &[Ident]) -> TokenStream {
+    match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|f| &f.ident);
+            quote!(#path { #(#idents: #names),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#names),*)),
+        Fields::Unit => path,
+    }
+}
+
+// Named fields become an object, one field is transparent, several an array.
+fn fields_into_js(krate: &TokenStream, fields: &Fields, names: &[Ident]) -> Result<TokenStream> {
+    Ok(match fields {
+        Fields::Named(named) => {
+            let keys = named
+                .named
+                .iter()
+                .map(|f| js_name(&f.attrs, f.ident.as_ref().unwrap()))
+                .collect::<Result<Vec<_>>>()?;
+            quote! {{
+                let object = #krate::value::Object::new(guard)?;
+                #(object.set(guard, #keys, &#krate::value::IntoJs::into_js(#names, guard)?)?;)*
+                Ok(object.into())
+            }}
+        }
+        Fields::Unnamed(_) if names.len() == 1 => quote!(#krate::value::IntoJs::into_js(#(#names)*, guard)),
+        Fields::Unnamed(_) => quote!(#krate::value::IntoJs::into_js((#(#names,)*), guard)),
+        Fields::Unit => quote!(#krate::value::IntoJs::into_js((), guard)),
+    })
+}
+
+// Reads `fields` back from `source`; evaluates to `Result<Self>`.
+fn fields_from_js(krate: &TokenStream, fields: &Fields, path: TokenStream, source: TokenStream) -> Result<TokenStream> {
+    let names = bindings(fields);
+    let construct = pattern(path, fields, &names);
+    Ok(match fields {
+        Fields::Named(named) => {
+            let keys = named
+                .named
+                .iter()
+                .map(|f| js_name(&f.attrs, f.ident.as_ref().unwrap()))
+                .collect::<Result<Vec<_>>>()?;
+            let contexts: Vec<String> = keys.iter().map(|k| format!("property {:?}", k.value())).collect();
+            let types = named.named.iter().map(|f| &f.ty);
+            quote! {{
+                let source = #source;
+                let object = source.as_object(guard).ok_or_else(|| {
+                    #krate::type_err(format!("expected an object, got {}", source.type_name(guard)))
+                })?;
+                #(
+                    let #names = <#types as #krate::value::FromJs<'__g>>::from_js(guard, &object.get(guard, #keys)?)
+                        .map_err(|e| #krate::value::within(e, #contexts))?;
+                )*
+                Ok(#construct)
+            }}
+        }
+        Fields::Unnamed(unnamed) => {
+            let types: Vec<_> = unnamed.unnamed.iter().map(|f| &f.ty).collect();
+            if types.len() == 1 {
+                quote! {{
+                    let #(#names)* = <#(#types)* as #krate::value::FromJs<'__g>>::from_js(guard, &#source)?;
+                    Ok(#construct)
+                }}
+            } else {
+                quote! {{
+                    let (#(#names,)*) = <(#(#types,)*) as #krate::value::FromJs<'__g>>::from_js(guard, &#source)?;
+                    Ok(#construct)
+                }}
+            }
+        }
+        Fields::Unit => quote!(Ok(#construct)),
+    })
+}
+
+pub fn into_js(input: DeriveInput) -> Result<TokenStream> {
+    check_data(&input, "IntoJs")?;
+    let krate = container_krate(&input)?;
+    let ident = &input.ident;
+    let generics = impl_generics(&input.generics, quote!(#krate::value::IntoJs<'__g>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let names = bindings(&data.fields);
+            let pattern = pattern(quote!(Self), &data.fields, &names);
+            let value = fields_into_js(&krate, &data.fields, &names)?;
+            quote! {
+                let #pattern = self;
+                #value
+            }
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let name = js_name(&variant.attrs, &variant.ident)?;
+                let names = bindings(&variant.fields);
+                let v = &variant.ident;
+                let pattern = pattern(quote!(Self::#v), &variant.fields, &names);
+                arms.push(if let Fields::Unit = variant.fields {
+                    quote!(#pattern => #krate::value::IntoJs::into_js(#name, guard))
+                } else {
+                    let payload = fields_into_js(&krate, &variant.fields, &names)?;
+                    quote! {
+                        #pattern => {
+                            let payload: #krate::Result<#krate::value::Value<'__g>> = #payload;
+                            let object = #krate::value::Object::new(guard)?;
+                            object.set(guard, #name, &payload?)?;
+                            Ok(object.into())
+                        }
+                    }
+                });
+            }
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::value::IntoJs<'__g> for #ident #ty_generics #where_clause {
+            fn into_js(self, guard: &'__g #krate::Guard<'_>) -> #krate::Result<#krate::value::Value<'__g>> {
+                #body
+            }
+        }
+    })
+}
+
+pub fn from_js(input: DeriveInput) -> Result<TokenStream> {
+    check_data(&input, "FromJs")?;
+    let krate = container_krate(&input)?;
+    let ident = &input.ident;
+    let generics = impl_generics(&input.generics, quote!(#krate::value::FromJs<'__g>));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let read = fields_from_js(&krate, &data.fields, quote!(Self), quote!(*value))?;
+            if let Fields::Unit = data.fields {
+                quote! {
+                    let _ = value;
+                    #read
+                }
+            } else {
+                read
+            }
+        }
+        Data::Enum(data) => {
+            let type_name = ident.to_string();
+            let mut unit_arms = Vec::new();
+            let mut payload_arms = Vec::new();
+            let mut expected = Vec::new();
+            for variant in &data.variants {
+                let name = js_name(&variant.attrs, &variant.ident)?;
+                let v = &variant.ident;
+                expected.push(format!("{:?}", name.value()));
+                if let Fields::Unit = variant.fields {
+                    unit_arms.push(quote!(#name => Ok(Self::#v)));
+                } else {
+                    let context = format!("variant {:?}", name.value());
+                    let read = fields_from_js(&krate, &variant.fields, quote!(Self::#v), quote!(payload))?;
+                    payload_arms.push(quote! {
+                        #name => {
+                            let read: #krate::Result<Self> = #read;
+                            read.map_err(|e| #krate::value::within(e, #context))
+                        }
+                    });
+                }
+            }
+            let expected = expected.join(", ");
+            let unknown = quote! {
+                other => Err(#krate::type_err(format!(
+                    "unknown {} variant {:?}, expected one of {}",
+                    #type_name, other, #expected
+                )))
+            };
+
+            let strings = (!unit_arms.is_empty()).then(|| {
+                quote! {
+                    if value.as_string(guard).is_some() {
+                        let tag = <::std::string::String as #krate::value::FromJs<'__g>>::from_js(guard, value)?;
+                        return match tag.as_str() {
+                            #(#unit_arms,)*
+                            #unknown,
+                        };
+                    }
+                }
+            });
+            let objects = if payload_arms.is_empty() {
+                quote! {
+                    Err(#krate::type_err(format!("expected a string, got {}", value.type_name(guard))))
+                }
+            } else {
+                quote! {
+                    let object = value.as_object(guard).ok_or_else(|| {
+                        #krate::type_err(format!("expected a {} variant, got {}", #type_name, value.type_name(guard)))
+                    })?;
+                    let keys = object.get_own_property_names(guard)?;
+                    let keys = <::std::vec::Vec<::std::string::String> as #krate::value::FromJs<'__g>>::from_js(
+                        guard,
+                        keys.as_value(),
+                    )?;
+                    let [tag] = keys.as_slice() else {
+                        return Err(#krate::type_err(format!(
+                            "expected an object with a single {} variant key, got {} keys",
+                            #type_name,
+                            keys.len()
+                        )));
+                    };
+                    let payload = object.get(guard, tag.as_str())?;
+                    match tag.as_str() {
+                        #(#payload_arms,)*
+                        #unknown,
+                    }
+                }
+            };
+            quote! {
+                #strings
+                #objects
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::value::FromJs<'__g> for #ident #ty_generics #where_clause {
+            fn from_js(
+                guard: &#krate::Guard<'_>,
+                value: &#krate::value::Value<'__g>,
+            ) -> #krate::Result<Self> {
+                #body
+            }
+        }
+    })
+}
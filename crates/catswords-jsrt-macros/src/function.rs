@@ -0,0 +1,55 @@
+use crate::util::{check_signature, into_js, take_args, MacroOptions};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, FnArg, ItemFn, Result};
+
+// Keeps the function and adds a sibling module of the same name (modules and
+// functions live in different namespaces) holding the glue.
+pub fn expand(func: ItemFn, options: MacroOptions) -> Result<TokenStream> {
+    check_signature(&func.sig, "#[js_function]")?;
+    let mut types = Vec::new();
+    for arg in &func.sig.inputs {
+        match arg {
+            FnArg::Typed(arg) => types.push(&*arg.ty),
+            FnArg::Receiver(receiver) => {
+                return Err(Error::new_spanned(
+                    receiver,
+                    "#[js_function] cannot be used on methods; put #[js_methods] on the impl block instead",
+                ));
+            }
+        }
+    }
+
+    let krate = options.krate();
+    let vis = &func.vis;
+    let ident = &func.sig.ident;
+    let name = options.or(ident);
+    let (glue, args) = take_args(&krate, &types);
+    let call = into_js(&krate, quote!(super::#ident(#(#args),*)), &func.sig.output);
+    let doc = format!("JavaScript binding of [`{}`], exposed to script as `{}`.", ident, name.value());
+
+    Ok(quote! {
+        #func
+
+        #[doc = #doc]
+        #[allow(dead_code, unused_imports)]
+        #vis mod #ident {
+            use super::*;
+
+            pub fn function<'g>(
+                guard: &'g #krate::Guard<'_>,
+            ) -> #krate::Result<#krate::value::Function<'g>> {
+                Ok(#krate::value::Function::new(guard, ::std::boxed::Box::new(|guard, info| {
+                    #glue
+                    #call
+                })))
+            }
+
+            pub fn register(context: &#krate::Context<'_>) -> #krate::Result<()> {
+                let guard = context.make_current()?;
+                let function = function(&guard)?;
+                context.set_global(#name, function.as_value())
+            }
+        }
+    })
+}
@@ -0,0 +1,64 @@
+//! Macros generating `catswords-jsrt` bindings. Use them through the `macros`
+//! feature of `catswords-jsrt`, which re-exports them:
+//!
+//! * `#[js_function]` on a free function adds a module of the same name with
+//!   `function(&guard)` and `register(&context)`.
+//! * `#[js_class]` on a struct and `#[js_methods]` on its impl block implement
+//!   `JsClass` / `JsMethods`, for `context.register_class::<T>()`.
+//! * `#[derive(FromJs, IntoJs)]` converts structs to and from plain objects,
+//!   and enums to strings (unit variants) or `{ Variant: payload }` objects.
+//!
+//! Parameters must implement `Param` (`FromJs` for every guard lifetime, or
+//! `Rest<T>`) and return types `IntoJs`, optionally wrapped in `Result`.
+//!
+//! Generated code refers to `::catswords_jsrt`. If the crate is renamed or only
+//! reachable through a re-export, pass its path: `#[js_function(crate = "my::jsrt")]`
+//! (likewise for `#[js_class]` and `#[js_methods]`), or `#[js(crate = "my::jsrt")]`
+//! on a derived type.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn, ItemImpl};
+
+mod class;
+mod derive;
+mod function;
+mod util;
+
+#[proc_macro_attribute]
+pub fn js_function(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = util::MacroOptions::default();
+    let parser = options.parser("js_function", true);
+    parse_macro_input!(attr with parser);
+    let func = parse_macro_input!(item as ItemFn);
+    function::expand(func, options).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[proc_macro_attribute]
+pub fn js_class(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = util::MacroOptions::default();
+    let parser = options.parser("js_class", true);
+    parse_macro_input!(attr with parser);
+    let item = parse_macro_input!(item as syn::Item);
+    class::expand_class(item, options).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[proc_macro_attribute]
+pub fn js_methods(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = util::MacroOptions::default();
+    let parser = options.parser("js_methods", false);
+    parse_macro_input!(attr with parser);
+    let block = parse_macro_input!(item as ItemImpl);
+    class::expand_methods(block, options).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[proc_macro_derive(FromJs, attributes(js))]
+pub fn derive_from_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::from_js(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[proc_macro_derive(IntoJs, attributes(js))]
+pub fn derive_into_js(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::into_js(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -0,0 +1,198 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, FnArg, Ident, LitStr, Path, Result, ReturnType, Signature, Type};
+
+// Path of the runtime crate in generated code; `crate = "..."` overrides it for
+// callers that rename or re-export `catswords-jsrt`.
+pub fn krate(path: Option<&Path>) -> TokenStream {
+    match path {
+        Some(path) => quote!(#path),
+        None => quote!(::catswords_jsrt),
+    }
+}
+
+fn parse_crate(meta: &ParseNestedMeta<'_>) -> Result<Path> {
+    meta.value()?.parse::<LitStr>()?.parse()
+}
+
+// Arguments of the attribute macros: `name = "..."` (unless `with_name` is
+// false) and `crate = "..."`.
+#[derive(Default)]
+pub struct MacroOptions {
+    pub name: Option<LitStr>,
+    pub krate: Option<Path>,
+}
+
+impl MacroOptions {
+    pub fn parser<'a>(&'a mut self, macro_name: &'static str, with_name: bool) -> impl Parser<Output = ()> + 'a {
+        syn::meta::parser(move |meta| {
+            if with_name && meta.path.is_ident("name") {
+                self.name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("crate") {
+                self.krate = Some(parse_crate(&meta)?);
+                Ok(())
+            } else if with_name {
+                Err(meta.error(format!(
+                    "unsupported {} attribute, expected `name = \"...\"` or `crate = \"...\"`",
+                    macro_name
+                )))
+            } else {
+                Err(meta.error(format!("unsupported {} attribute, expected `crate = \"...\"`", macro_name)))
+            }
+        })
+    }
+
+    pub fn or(&self, ident: &Ident) -> LitStr {
+        self.name.clone().unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()))
+    }
+
+    pub fn krate(&self) -> TokenStream {
+        krate(self.krate.as_ref())
+    }
+}
+
+// `#[js(...)]` helper attributes on methods, fields and variants.
+#[derive(Default)]
+pub struct JsAttrs {
+    pub name: Option<LitStr>,
+    pub krate: Option<Path>,
+    pub constructor: Option<Span>,
+    pub get: Option<Span>,
+    pub set: Option<Span>,
+    pub skip: Option<Span>,
+}
+
+impl JsAttrs {
+    // `allowed` lists the flags valid where the attribute appears, and `crate`
+    // where that is; `name` always is.
+    pub fn parse(attrs: &[Attribute], allowed: &[&str]) -> Result<Self> {
+        let mut out = JsAttrs::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("js")) {
+            attr.parse_nested_meta(|meta| out.apply(meta, allowed))?;
+        }
+        Ok(out)
+    }
+
+    fn apply(&mut self, meta: ParseNestedMeta<'_>, allowed: &[&str]) -> Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        if allowed.contains(&"crate") && meta.path.is_ident("crate") {
+            self.krate = Some(parse_crate(&meta)?);
+            return Ok(());
+        }
+        let flag = match meta.path.get_ident() {
+            Some(ident) if allowed.contains(&ident.to_string().as_str()) => ident.to_string(),
+            _ => {
+                let mut expected = vec!["`name = \"...\"`".to_string()];
+                expected.extend(allowed.iter().map(|a| match *a {
+                    "crate" => "`crate = \"...\"`".to_string(),
+                    flag => format!("`{}`", flag),
+                }));
+                return Err(meta.error(format!("unsupported js attribute, expected {}", expected.join(", "))));
+            }
+        };
+        let span = Some(meta.path.span());
+        match flag.as_str() {
+            "constructor" => self.constructor = span,
+            "get" => self.get = span,
+            "set" => self.set = span,
+            "skip" => self.skip = span,
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
+
+pub fn strip_js_attrs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|a| !a.path().is_ident("js"));
+}
+
+// Whether a function returns `Result<..>` (by name: the error is propagated)
+// rather than a plain value.
+pub fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+// Rejects signatures a binding cannot call.
+pub fn check_signature(sig: &Signature, macro_name: &str) -> Result<()> {
+    if let Some(token) = &sig.asyncness {
+        return Err(Error::new_spanned(token, format!("{} does not support async functions", macro_name)));
+    }
+    if sig.generics.type_params().next().is_some() || sig.generics.const_params().next().is_some() {
+        return Err(Error::new_spanned(&sig.generics, format!("{} does not support generic functions", macro_name)));
+    }
+    if let Some(variadic) = &sig.variadic {
+        return Err(Error::new_spanned(variadic, format!("{} does not support variadic functions", macro_name)));
+    }
+    let typed: Vec<&Type> = sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(arg) => Some(&*arg.ty),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    for (i, ty) in typed.iter().enumerate() {
+        if let Type::ImplTrait(_) = ty {
+            return Err(Error::new_spanned(ty, format!("{} does not support `impl Trait` parameters", macro_name)));
+        }
+        // Renamed imports of `Rest` are caught by the const assertion in `take_args`.
+        if i + 1 < typed.len() && is_rest(ty) {
+            return Err(Error::new_spanned(ty, "`Rest<T>` must be the last parameter"));
+        }
+    }
+    Ok(())
+}
+
+fn is_rest(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == "Rest"),
+        _ => false,
+    }
+}
+
+// Argument conversion for a native callback with `guard` and `info` in scope:
+// the arity check followed by one `Param::take` per parameter.
+pub fn take_args(krate: &TokenStream, types: &[&Type]) -> (TokenStream, Vec<Ident>) {
+    let names: Vec<Ident> = (0..types.len()).map(|i| format_ident!("arg{}", i)).collect();
+    let takes = if types.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            let mut index = 0usize;
+            #(let #names = <#types as #krate::value::Param>::take(guard, &info.arguments, &mut index)?;)*
+        }
+    };
+    let glue = quote! {
+        const {
+            assert!(
+                #krate::value::rest_is_last(&[#(<#types as #krate::value::Param>::REST),*]),
+                "Rest<T> must be the last parameter",
+            )
+        };
+        #krate::value::check_arity(info.arguments.len(), &[#(<#types as #krate::value::Param>::REQUIRED),*])?;
+        #takes
+    };
+    (glue, names)
+}
+
+// `call` evaluated to the callback's return value, converted for script.
+pub fn into_js(krate: &TokenStream, call: TokenStream, output: &ReturnType) -> TokenStream {
+    if returns_result(output) {
+        quote!(#krate::value::IntoJs::into_js(#call?, guard))
+    } else {
+        quote!(#krate::value::IntoJs::into_js(#call, guard))
+    }
+}
@@ -0,0 +1,8 @@
+use catswords_jsrt::js_class;
+
+#[js_class]
+struct Stack<T> {
+    items: Vec<T>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use catswords_jsrt::value::IntoJs;
+
+#[derive(IntoJs)]
+#[js(crate = "missing::jsrt")]
+struct Point {
+    x: f64,
+}
+
+fn main() {}
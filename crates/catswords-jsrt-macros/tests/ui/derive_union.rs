@@ -0,0 +1,9 @@
+use catswords_jsrt::value::IntoJs;
+
+#[derive(IntoJs)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
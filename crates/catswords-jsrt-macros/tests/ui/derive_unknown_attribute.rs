@@ -0,0 +1,10 @@
+use catswords_jsrt::value::FromJs;
+
+#[derive(FromJs)]
+struct Point {
+    x: f64,
+    #[js(skip)]
+    y: f64,
+}
+
+fn main() {}
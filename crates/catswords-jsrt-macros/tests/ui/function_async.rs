@@ -0,0 +1,8 @@
+use catswords_jsrt::js_function;
+
+#[js_function]
+async fn fetch(url: String) -> String {
+    url
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use catswords_jsrt::js_function;
+
+#[js_function]
+fn identity<T>(value: T) -> T {
+    value
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use catswords_jsrt::js_function;
+
+#[js_function]
+fn join(parts: catswords_jsrt::value::Rest<String>, separator: String) -> String {
+    parts.join(&separator)
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use catswords_jsrt::js_function;
+
+#[js_function(rename = "addNumbers")]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+fn main() {}
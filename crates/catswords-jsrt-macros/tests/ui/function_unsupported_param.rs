@@ -0,0 +1,10 @@
+use catswords_jsrt::js_function;
+
+struct Handle;
+
+#[js_function]
+fn close(handle: Handle) {
+    let _ = handle;
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use catswords_jsrt::{js_class, js_methods};
+
+#[js_class]
+struct Counter {
+    n: i64,
+}
+
+#[js_methods]
+impl Counter {
+    fn into_value(self) -> i64 {
+        self.n
+    }
+}
+
+fn main() {}
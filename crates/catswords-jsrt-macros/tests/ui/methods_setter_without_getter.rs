@@ -0,0 +1,16 @@
+use catswords_jsrt::{js_class, js_methods};
+
+#[js_class]
+struct Counter {
+    n: i64,
+}
+
+#[js_methods]
+impl Counter {
+    #[js(set)]
+    fn set_n(&mut self, n: i64) {
+        self.n = n;
+    }
+}
+
+fn main() {}
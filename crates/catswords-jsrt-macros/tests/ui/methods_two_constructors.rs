@@ -0,0 +1,21 @@
+use catswords_jsrt::{js_class, js_methods};
+
+#[js_class]
+struct Counter {
+    n: i64,
+}
+
+#[js_methods]
+impl Counter {
+    #[js(constructor)]
+    fn new() -> Self {
+        Counter { n: 0 }
+    }
+
+    #[js(constructor)]
+    fn starting_at(n: i64) -> Self {
+        Counter { n }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use catswords_jsrt::js_methods;
+
+struct Counter {
+    n: i64,
+}
+
+#[js_methods]
+impl Counter {
+    fn value(&self) -> i64 {
+        self.n
+    }
+}
+
+fn main() {}
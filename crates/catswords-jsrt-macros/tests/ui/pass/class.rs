@@ -0,0 +1,54 @@
+use catswords_jsrt::{js_class, js_methods, Result};
+
+#[js_class(name = "Counter")]
+struct RustCounter {
+    n: i64,
+}
+
+#[js_methods]
+impl RustCounter {
+    #[js(constructor)]
+    fn new(start: Option<i64>) -> Self {
+        RustCounter { n: start.unwrap_or(0) }
+    }
+
+    fn increment(&mut self) -> i64 {
+        self.n += 1;
+        self.n
+    }
+
+    #[js(get)]
+    fn value(&self) -> i64 {
+        self.n
+    }
+
+    #[js(set)]
+    fn set_value(&mut self, n: i64) -> Result<()> {
+        self.n = n;
+        Ok(())
+    }
+
+    #[js(name = "zero")]
+    fn make_zero() -> i64 {
+        0
+    }
+
+    #[js(skip)]
+    #[allow(dead_code)]
+    fn internal(&self) -> &i64 {
+        &self.n
+    }
+}
+
+// No constructor: `new` throws from script.
+#[js_class]
+struct Handle;
+
+#[js_methods]
+impl Handle {
+    fn id(&self) -> u32 {
+        1
+    }
+}
+
+fn main() {}
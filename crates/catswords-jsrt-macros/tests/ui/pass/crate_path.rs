@@ -0,0 +1,35 @@
+// The runtime crate reached through a re-export instead of `::catswords_jsrt`.
+mod bindings {
+    pub use catswords_jsrt as jsrt;
+}
+
+use bindings::jsrt::value::{FromJs, IntoJs};
+use bindings::jsrt::{js_class, js_function, js_methods};
+
+#[js_function(crate = "crate::bindings::jsrt")]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[js_class(crate = "crate::bindings::jsrt", name = "Counter")]
+struct Counter {
+    n: i64,
+}
+
+#[js_methods(crate = "crate::bindings::jsrt")]
+impl Counter {
+    #[js(get)]
+    fn value(&self) -> i64 {
+        self.n
+    }
+}
+
+#[derive(FromJs, IntoJs)]
+#[js(crate = "crate::bindings::jsrt")]
+struct Point {
+    x: f64,
+}
+
+fn main() {
+    let _ = (add::function, Counter { n: 0 }, Point { x: 0.0 }.x);
+}
@@ -0,0 +1,37 @@
+use catswords_jsrt::value::{FromJs, IntoJs};
+
+#[derive(FromJs, IntoJs)]
+struct Point {
+    x: f64,
+    #[js(name = "Y")]
+    y: f64,
+}
+
+#[derive(FromJs, IntoJs)]
+struct Meters(f64);
+
+#[derive(FromJs, IntoJs)]
+struct Pair(i32, String);
+
+#[derive(FromJs, IntoJs)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(FromJs, IntoJs)]
+enum Shape {
+    Empty,
+    #[js(name = "dot")]
+    Dot,
+    Circle { center: Point, radius: f64 },
+    Square(f64),
+}
+
+#[derive(FromJs, IntoJs)]
+struct Unit;
+
+fn main() {
+    let _ = (Meters(1.0), Pair(1, String::new()), Wrapper { inner: Unit }, Shape::Square(1.0));
+    let _ = Shape::Circle { center: Point { x: 0.0, y: 0.0 }, radius: 1.0 };
+    let _ = (Shape::Empty, Shape::Dot);
+}
@@ -0,0 +1,24 @@
+use catswords_jsrt::value::Rest;
+use catswords_jsrt::{js_function, Result};
+
+#[js_function]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[js_function(name = "sumAll")]
+pub fn sum(first: f64, rest: Rest<f64>) -> f64 {
+    first + rest.0.iter().sum::<f64>()
+}
+
+#[js_function]
+fn greet(name: Option<String>) -> Result<String> {
+    Ok(format!("hello {}", name.unwrap_or_default()))
+}
+
+#[js_function]
+fn nothing() {}
+
+fn main() {
+    let _ = (add::register, sum::function, greet::register, nothing::function);
+}
@@ -17,4 +17,36 @@ fn main() {
     // Common Windows import library name: ChakraCore.lib
     // If your import library name differs, adjust this line.
     println!("cargo:rustc-link-lib=dylib=ChakraCore");
+
+    #[cfg(feature = "bindgen")]
+    generate_bindings(&inc);
+}
+
+// Regenerate the function declarations from the installed headers.
+// Every type is hand-maintained in lib.rs, so all of them are blocklisted and
+// the generated file only contains `extern "C"` blocks referring to them.
+#[cfg(feature = "bindgen")]
+fn generate_bindings(inc: &str) {
+    let out = std::path::PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
+
+    bindgen::Builder::default()
+        .header_contents("jsrt.h", "#include <ChakraCore.h>\n#include <ChakraDebug.h>\n")
+        .clang_arg(format!("-I{}", inc))
+        .allowlist_function("Js.*")
+        .blocklist_function("JsTTD.*")
+        .blocklist_type("_?Js.*")
+        .blocklist_type("FetchImportedModuleCallBack")
+        .blocklist_type("FetchImportedModuleFromScriptCallBack")
+        .blocklist_type("NotifyModuleReadyCallback")
+        .blocklist_type("InitializeImportMetaCallback")
+        .blocklist_type("ReportModuleCompletionCallback")
+        .size_t_is_usize(true)
+        .layout_tests(false)
+        .generate_comments(false)
+        .merge_extern_blocks(true)
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
+        .generate()
+        .expect("failed to generate ChakraCore bindings")
+        .write_to_file(out)
+        .expect("failed to write ChakraCore bindings");
 }
@@ -0,0 +1,696 @@
+// Pregenerated JsRT function bindings for ChakraCommon.h, ChakraCore.h and
+// ChakraDebug.h (time-travel debugging `JsTTD*` entry points excluded).
+//
+// Regenerate with `cargo build -p catswords-jsrt-sys --features bindgen` and
+// copy `$OUT_DIR/bindings.rs` over this file. `tests/bindings.rs` checks every
+// declaration here against the headers.
+//
+// Types are hand-maintained in `lib.rs` and referred to by name.
+
+// ChakraCommon.h
+
+unsafe extern "C" {
+    pub fn JsCreateRuntime(
+        attributes: JsRuntimeAttributes,
+        threadService: JsThreadServiceCallback,
+        runtime: *mut JsRuntimeHandle,
+    ) -> JsErrorCode;
+
+    pub fn JsCollectGarbage(runtime: JsRuntimeHandle) -> JsErrorCode;
+
+    pub fn JsDisposeRuntime(runtime: JsRuntimeHandle) -> JsErrorCode;
+
+    pub fn JsGetRuntimeMemoryUsage(runtime: JsRuntimeHandle, memoryUsage: *mut usize) -> JsErrorCode;
+
+    pub fn JsGetRuntimeMemoryLimit(runtime: JsRuntimeHandle, memoryLimit: *mut usize) -> JsErrorCode;
+
+    pub fn JsSetRuntimeMemoryLimit(runtime: JsRuntimeHandle, memoryLimit: usize) -> JsErrorCode;
+
+    pub fn JsSetRuntimeMemoryAllocationCallback(
+        runtime: JsRuntimeHandle,
+        callbackState: *mut ::std::os::raw::c_void,
+        allocationCallback: JsMemoryAllocationCallback,
+    ) -> JsErrorCode;
+
+    pub fn JsSetRuntimeBeforeCollectCallback(
+        runtime: JsRuntimeHandle,
+        callbackState: *mut ::std::os::raw::c_void,
+        beforeCollectCallback: JsBeforeCollectCallback,
+    ) -> JsErrorCode;
+
+    pub fn JsAddRef(ref_: JsRef, count: *mut ::std::os::raw::c_uint) -> JsErrorCode;
+
+    pub fn JsRelease(ref_: JsRef, count: *mut ::std::os::raw::c_uint) -> JsErrorCode;
+
+    pub fn JsSetObjectBeforeCollectCallback(
+        ref_: JsRef,
+        callbackState: *mut ::std::os::raw::c_void,
+        objectBeforeCollectCallback: JsObjectBeforeCollectCallback,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateContext(runtime: JsRuntimeHandle, newContext: *mut JsContextRef) -> JsErrorCode;
+
+    pub fn JsGetCurrentContext(currentContext: *mut JsContextRef) -> JsErrorCode;
+
+    pub fn JsSetCurrentContext(context: JsContextRef) -> JsErrorCode;
+
+    pub fn JsGetContextOfObject(object: JsValueRef, context: *mut JsContextRef) -> JsErrorCode;
+
+    pub fn JsGetContextData(context: JsContextRef, data: *mut *mut ::std::os::raw::c_void) -> JsErrorCode;
+
+    pub fn JsSetContextData(context: JsContextRef, data: *mut ::std::os::raw::c_void) -> JsErrorCode;
+
+    pub fn JsGetRuntime(context: JsContextRef, runtime: *mut JsRuntimeHandle) -> JsErrorCode;
+
+    pub fn JsIdle(nextIdleTick: *mut ::std::os::raw::c_uint) -> JsErrorCode;
+
+    pub fn JsGetSymbolFromPropertyId(propertyId: JsPropertyIdRef, symbol: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetPropertyIdType(
+        propertyId: JsPropertyIdRef,
+        propertyIdType: *mut JsPropertyIdType,
+    ) -> JsErrorCode;
+
+    pub fn JsGetPropertyIdFromSymbol(symbol: JsValueRef, propertyId: *mut JsPropertyIdRef) -> JsErrorCode;
+
+    pub fn JsCreateSymbol(description: JsValueRef, result: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetOwnPropertySymbols(object: JsValueRef, propertySymbols: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetUndefinedValue(undefinedValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetNullValue(nullValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetTrueValue(trueValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetFalseValue(falseValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsBoolToBoolean(value: bool, booleanValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsBooleanToBool(value: JsValueRef, boolValue: *mut bool) -> JsErrorCode;
+
+    pub fn JsConvertValueToBoolean(value: JsValueRef, booleanValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetValueType(value: JsValueRef, type_: *mut JsValueType) -> JsErrorCode;
+
+    pub fn JsDoubleToNumber(doubleValue: f64, value: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsIntToNumber(intValue: ::std::os::raw::c_int, value: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsNumberToDouble(value: JsValueRef, doubleValue: *mut f64) -> JsErrorCode;
+
+    pub fn JsNumberToInt(value: JsValueRef, intValue: *mut ::std::os::raw::c_int) -> JsErrorCode;
+
+    pub fn JsConvertValueToNumber(value: JsValueRef, numberValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetStringLength(stringValue: JsValueRef, length: *mut ::std::os::raw::c_int) -> JsErrorCode;
+
+    pub fn JsConvertValueToString(value: JsValueRef, stringValue: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetGlobalObject(globalObject: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateObject(object: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateExternalObject(
+        data: *mut ::std::os::raw::c_void,
+        finalizeCallback: JsFinalizeCallback,
+        object: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsConvertValueToObject(value: JsValueRef, object: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetPrototype(object: JsValueRef, prototypeObject: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsSetPrototype(object: JsValueRef, prototypeObject: JsValueRef) -> JsErrorCode;
+
+    pub fn JsInstanceOf(object: JsValueRef, constructor: JsValueRef, result: *mut bool) -> JsErrorCode;
+
+    pub fn JsGetExtensionAllowed(object: JsValueRef, value: *mut bool) -> JsErrorCode;
+
+    pub fn JsPreventExtension(object: JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetProperty(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        value: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsGetOwnPropertyDescriptor(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        propertyDescriptor: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsGetOwnPropertyNames(object: JsValueRef, propertyNames: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsSetProperty(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        value: JsValueRef,
+        useStrictRules: bool,
+    ) -> JsErrorCode;
+
+    pub fn JsHasProperty(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        hasProperty: *mut bool,
+    ) -> JsErrorCode;
+
+    pub fn JsDeleteProperty(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        useStrictRules: bool,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsDefineProperty(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        propertyDescriptor: JsValueRef,
+        result: *mut bool,
+    ) -> JsErrorCode;
+
+    pub fn JsHasIndexedProperty(object: JsValueRef, index: JsValueRef, result: *mut bool) -> JsErrorCode;
+
+    pub fn JsGetIndexedProperty(object: JsValueRef, index: JsValueRef, result: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsSetIndexedProperty(object: JsValueRef, index: JsValueRef, value: JsValueRef) -> JsErrorCode;
+
+    pub fn JsDeleteIndexedProperty(object: JsValueRef, index: JsValueRef) -> JsErrorCode;
+
+    pub fn JsHasIndexedPropertiesExternalData(object: JsValueRef, value: *mut bool) -> JsErrorCode;
+
+    pub fn JsGetIndexedPropertiesExternalData(
+        object: JsValueRef,
+        data: *mut *mut ::std::os::raw::c_void,
+        arrayType: *mut JsTypedArrayType,
+        elementLength: *mut ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsSetIndexedPropertiesToExternalData(
+        object: JsValueRef,
+        data: *mut ::std::os::raw::c_void,
+        arrayType: JsTypedArrayType,
+        elementLength: ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsEquals(object1: JsValueRef, object2: JsValueRef, result: *mut bool) -> JsErrorCode;
+
+    pub fn JsStrictEquals(object1: JsValueRef, object2: JsValueRef, result: *mut bool) -> JsErrorCode;
+
+    pub fn JsHasExternalData(object: JsValueRef, value: *mut bool) -> JsErrorCode;
+
+    pub fn JsGetExternalData(object: JsValueRef, externalData: *mut *mut ::std::os::raw::c_void) -> JsErrorCode;
+
+    pub fn JsSetExternalData(object: JsValueRef, externalData: *mut ::std::os::raw::c_void) -> JsErrorCode;
+
+    pub fn JsCreateArray(length: ::std::os::raw::c_uint, result: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateArrayBuffer(byteLength: ::std::os::raw::c_uint, result: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateExternalArrayBuffer(
+        data: *mut ::std::os::raw::c_void,
+        byteLength: ::std::os::raw::c_uint,
+        finalizeCallback: JsFinalizeCallback,
+        callbackState: *mut ::std::os::raw::c_void,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateTypedArray(
+        arrayType: JsTypedArrayType,
+        baseArray: JsValueRef,
+        byteOffset: ::std::os::raw::c_uint,
+        elementLength: ::std::os::raw::c_uint,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateDataView(
+        arrayBuffer: JsValueRef,
+        byteOffset: ::std::os::raw::c_uint,
+        byteLength: ::std::os::raw::c_uint,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsGetTypedArrayInfo(
+        typedArray: JsValueRef,
+        arrayType: *mut JsTypedArrayType,
+        arrayBuffer: *mut JsValueRef,
+        byteOffset: *mut ::std::os::raw::c_uint,
+        byteLength: *mut ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsGetArrayBufferStorage(
+        arrayBuffer: JsValueRef,
+        buffer: *mut *mut ::std::os::raw::c_uchar,
+        bufferLength: *mut ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsGetTypedArrayStorage(
+        typedArray: JsValueRef,
+        buffer: *mut *mut ::std::os::raw::c_uchar,
+        bufferLength: *mut ::std::os::raw::c_uint,
+        arrayType: *mut JsTypedArrayType,
+        elementSize: *mut ::std::os::raw::c_int,
+    ) -> JsErrorCode;
+
+    pub fn JsGetDataViewStorage(
+        dataView: JsValueRef,
+        buffer: *mut *mut ::std::os::raw::c_uchar,
+        bufferLength: *mut ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsDetachArrayBuffer(arrayBuffer: JsValueRef) -> JsErrorCode;
+
+    pub fn JsCallFunction(
+        function: JsValueRef,
+        arguments: *mut JsValueRef,
+        argumentCount: ::std::os::raw::c_ushort,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsConstructObject(
+        function: JsValueRef,
+        arguments: *mut JsValueRef,
+        argumentCount: ::std::os::raw::c_ushort,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateFunction(
+        nativeFunction: JsNativeFunction,
+        callbackState: *mut ::std::os::raw::c_void,
+        function: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateNamedFunction(
+        name: JsValueRef,
+        nativeFunction: JsNativeFunction,
+        callbackState: *mut ::std::os::raw::c_void,
+        function: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateRangeError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateReferenceError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateSyntaxError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateTypeError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateURIError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsHasException(hasException: *mut bool) -> JsErrorCode;
+
+    pub fn JsGetAndClearException(exception: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsSetException(exception: JsValueRef) -> JsErrorCode;
+
+    pub fn JsDisableRuntimeExecution(runtime: JsRuntimeHandle) -> JsErrorCode;
+
+    pub fn JsEnableRuntimeExecution(runtime: JsRuntimeHandle) -> JsErrorCode;
+
+    pub fn JsIsRuntimeExecutionDisabled(runtime: JsRuntimeHandle, isDisabled: *mut bool) -> JsErrorCode;
+
+    pub fn JsSetPromiseContinuationCallback(
+        promiseContinuationCallback: JsPromiseContinuationCallback,
+        callbackState: *mut ::std::os::raw::c_void,
+    ) -> JsErrorCode;
+}
+
+// ChakraCore.h
+
+unsafe extern "C" {
+    pub fn JsInitializeModuleRecord(
+        referencingModule: JsModuleRecord,
+        normalizedSpecifier: JsValueRef,
+        moduleRecord: *mut JsModuleRecord,
+    ) -> JsErrorCode;
+
+    pub fn JsParseModuleSource(
+        requestModule: JsModuleRecord,
+        sourceContext: JsSourceContext,
+        script: *mut ::std::os::raw::c_uchar,
+        scriptLength: ::std::os::raw::c_uint,
+        sourceFlag: JsParseModuleSourceFlags,
+        exceptionValueRef: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsModuleEvaluation(requestModule: JsModuleRecord, result: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsSetModuleHostInfo(
+        requestModule: JsModuleRecord,
+        moduleHostInfo: JsModuleHostInfoKind,
+        hostInfo: *mut ::std::os::raw::c_void,
+    ) -> JsErrorCode;
+
+    pub fn JsGetModuleHostInfo(
+        requestModule: JsModuleRecord,
+        moduleHostInfo: JsModuleHostInfoKind,
+        hostInfo: *mut *mut ::std::os::raw::c_void,
+    ) -> JsErrorCode;
+
+    pub fn JsGetAndClearExceptionWithMetadata(metadata: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsGetModuleNamespace(requestModule: JsModuleRecord, moduleNamespace: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateEnhancedFunction(
+        nativeFunction: JsEnhancedNativeFunction,
+        metadata: JsValueRef,
+        callbackState: *mut ::std::os::raw::c_void,
+        function: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateString(
+        content: *const ::std::os::raw::c_char,
+        length: usize,
+        value: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateStringUtf16(content: *const u16, length: usize, value: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCopyString(
+        value: JsValueRef,
+        buffer: *mut ::std::os::raw::c_char,
+        bufferSize: usize,
+        length: *mut usize,
+    ) -> JsErrorCode;
+
+    pub fn JsCopyStringUtf16(
+        value: JsValueRef,
+        start: ::std::os::raw::c_int,
+        length: ::std::os::raw::c_int,
+        buffer: *mut u16,
+        written: *mut usize,
+    ) -> JsErrorCode;
+
+    pub fn JsCopyStringOneByte(
+        value: JsValueRef,
+        start: ::std::os::raw::c_int,
+        length: ::std::os::raw::c_int,
+        buffer: *mut ::std::os::raw::c_char,
+        written: *mut usize,
+    ) -> JsErrorCode;
+
+    pub fn JsParse(
+        script: JsValueRef,
+        sourceContext: JsSourceContext,
+        sourceUrl: JsValueRef,
+        parseAttributes: JsParseScriptAttributes,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsRun(
+        script: JsValueRef,
+        sourceContext: JsSourceContext,
+        sourceUrl: JsValueRef,
+        parseAttributes: JsParseScriptAttributes,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreatePropertyId(
+        name: *const ::std::os::raw::c_char,
+        length: usize,
+        propertyId: *mut JsPropertyIdRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCopyPropertyId(
+        propertyId: JsPropertyIdRef,
+        buffer: *mut ::std::os::raw::c_char,
+        bufferSize: usize,
+        length: *mut usize,
+    ) -> JsErrorCode;
+
+    pub fn JsSerialize(
+        script: JsValueRef,
+        buffer: *mut JsValueRef,
+        parseAttributes: JsParseScriptAttributes,
+    ) -> JsErrorCode;
+
+    pub fn JsParseSerialized(
+        buffer: JsValueRef,
+        scriptLoadCallback: JsSerializedLoadScriptCallback,
+        sourceContext: JsSourceContext,
+        sourceUrl: JsValueRef,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsRunSerialized(
+        buffer: JsValueRef,
+        scriptLoadCallback: JsSerializedLoadScriptCallback,
+        sourceContext: JsSourceContext,
+        sourceUrl: JsValueRef,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsGetPromiseState(promise: JsValueRef, state: *mut JsPromiseState) -> JsErrorCode;
+
+    pub fn JsGetPromiseResult(promise: JsValueRef, result: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreatePromise(
+        promise: *mut JsValueRef,
+        resolveFunction: *mut JsValueRef,
+        rejectFunction: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsCreateWeakReference(value: JsValueRef, weakRef: *mut JsWeakRef) -> JsErrorCode;
+
+    pub fn JsGetWeakReferenceValue(weakRef: JsWeakRef, value: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsCreateSharedArrayBufferWithSharedContent(
+        sharedContents: JsSharedArrayBufferContentHandle,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsGetSharedArrayBufferContent(
+        sharedArrayBuffer: JsValueRef,
+        sharedContents: *mut JsSharedArrayBufferContentHandle,
+    ) -> JsErrorCode;
+
+    pub fn JsReleaseSharedArrayBufferContentHandle(sharedContents: JsSharedArrayBufferContentHandle) -> JsErrorCode;
+
+    pub fn JsHasOwnProperty(
+        object: JsValueRef,
+        propertyId: JsPropertyIdRef,
+        hasOwnProperty: *mut bool,
+    ) -> JsErrorCode;
+
+    pub fn JsGetDataViewInfo(
+        dataView: JsValueRef,
+        arrayBuffer: *mut JsValueRef,
+        byteOffset: *mut ::std::os::raw::c_uint,
+        byteLength: *mut ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsLessThan(object1: JsValueRef, object2: JsValueRef, result: *mut bool) -> JsErrorCode;
+
+    pub fn JsLessThanOrEqual(object1: JsValueRef, object2: JsValueRef, result: *mut bool) -> JsErrorCode;
+
+    pub fn JsCreateExternalObjectWithPrototype(
+        data: *mut ::std::os::raw::c_void,
+        finalizeCallback: JsFinalizeCallback,
+        prototype: JsValueRef,
+        object: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsObjectGetProperty(object: JsValueRef, key: JsValueRef, value: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsObjectSetProperty(
+        object: JsValueRef,
+        key: JsValueRef,
+        value: JsValueRef,
+        useStrictRules: bool,
+    ) -> JsErrorCode;
+
+    pub fn JsObjectHasProperty(object: JsValueRef, key: JsValueRef, hasProperty: *mut bool) -> JsErrorCode;
+
+    pub fn JsObjectDefineProperty(
+        object: JsValueRef,
+        key: JsValueRef,
+        propertyDescriptor: JsValueRef,
+        result: *mut bool,
+    ) -> JsErrorCode;
+
+    pub fn JsObjectDeleteProperty(
+        object: JsValueRef,
+        key: JsValueRef,
+        useStrictRules: bool,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsObjectGetOwnPropertyDescriptor(
+        object: JsValueRef,
+        key: JsValueRef,
+        propertyDescriptor: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsObjectHasOwnProperty(object: JsValueRef, key: JsValueRef, hasOwnProperty: *mut bool) -> JsErrorCode;
+
+    pub fn JsSetHostPromiseRejectionTracker(
+        promiseRejectionTrackerCallback: JsHostPromiseRejectionTrackerCallback,
+        callbackState: *mut ::std::os::raw::c_void,
+    ) -> JsErrorCode;
+
+    pub fn JsGetProxyProperties(
+        object: JsValueRef,
+        isProxy: *mut bool,
+        target: *mut JsValueRef,
+        handler: *mut JsValueRef,
+    ) -> JsErrorCode;
+}
+
+// ChakraDebug.h
+
+unsafe extern "C" {
+    pub fn JsDiagStartDebugging(
+        runtimeHandle: JsRuntimeHandle,
+        debugEventCallback: JsDiagDebugEventCallback,
+        callbackState: *mut ::std::os::raw::c_void,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagStopDebugging(
+        runtimeHandle: JsRuntimeHandle,
+        callbackState: *mut *mut ::std::os::raw::c_void,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagRequestAsyncBreak(runtimeHandle: JsRuntimeHandle) -> JsErrorCode;
+
+    pub fn JsDiagGetBreakpoints(breakpoints: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsDiagSetBreakpoint(
+        scriptId: ::std::os::raw::c_uint,
+        lineNumber: ::std::os::raw::c_uint,
+        columnNumber: ::std::os::raw::c_uint,
+        breakpoint: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagRemoveBreakpoint(breakpointId: ::std::os::raw::c_uint) -> JsErrorCode;
+
+    pub fn JsDiagSetBreakOnException(
+        runtimeHandle: JsRuntimeHandle,
+        exceptionAttributes: JsDiagBreakOnExceptionAttributes,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagGetBreakOnException(
+        runtimeHandle: JsRuntimeHandle,
+        exceptionAttributes: *mut JsDiagBreakOnExceptionAttributes,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagSetStepType(stepType: JsDiagStepType) -> JsErrorCode;
+
+    pub fn JsDiagGetScripts(scriptsArray: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsDiagGetSource(scriptId: ::std::os::raw::c_uint, source: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsDiagGetFunctionPosition(function: JsValueRef, functionPosition: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsDiagGetStackTrace(stackTrace: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsDiagGetStackProperties(
+        stackFrameIndex: ::std::os::raw::c_uint,
+        properties: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagGetProperties(
+        objectHandle: ::std::os::raw::c_uint,
+        fromCount: ::std::os::raw::c_uint,
+        totalCount: ::std::os::raw::c_uint,
+        propertiesObject: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsDiagGetObjectFromHandle(objectHandle: ::std::os::raw::c_uint, handleObject: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsDiagEvaluate(
+        expression: JsValueRef,
+        stackFrameIndex: ::std::os::raw::c_uint,
+        parseAttributes: JsParseScriptAttributes,
+        forceSetValueProp: bool,
+        evalResult: *mut JsValueRef,
+    ) -> JsErrorCode;
+}
+
+// ChakraCommonWindows.h (wchar_t entry points, Windows builds only)
+
+#[cfg(windows)]
+unsafe extern "C" {
+    pub fn JsParseScript(
+        script: *const u16,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsParseScriptWithAttributes(
+        script: *const u16,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        parseAttributes: JsParseScriptAttributes,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsRunScript(
+        script: *const u16,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsExperimentalApiRunModule(
+        script: *const u16,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsSerializeScript(
+        script: *const u16,
+        buffer: *mut ::std::os::raw::c_uchar,
+        bufferSize: *mut ::std::os::raw::c_uint,
+    ) -> JsErrorCode;
+
+    pub fn JsParseSerializedScriptWithCallback(
+        scriptLoadCallback: JsSerializedScriptLoadSourceCallback,
+        scriptUnloadCallback: JsSerializedScriptUnloadCallback,
+        buffer: *mut ::std::os::raw::c_uchar,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsRunSerializedScriptWithCallback(
+        scriptLoadCallback: JsSerializedScriptLoadSourceCallback,
+        scriptUnloadCallback: JsSerializedScriptUnloadCallback,
+        buffer: *mut ::std::os::raw::c_uchar,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsParseSerializedScript(
+        script: *const u16,
+        buffer: *mut ::std::os::raw::c_uchar,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsRunSerializedScript(
+        script: *const u16,
+        buffer: *mut ::std::os::raw::c_uchar,
+        sourceContext: JsSourceContext,
+        sourceUrl: *const u16,
+        result: *mut JsValueRef,
+    ) -> JsErrorCode;
+
+    pub fn JsGetPropertyIdFromName(name: *const u16, propertyId: *mut JsPropertyIdRef) -> JsErrorCode;
+
+    pub fn JsGetPropertyNameFromId(propertyId: JsPropertyIdRef, name: *mut *const u16) -> JsErrorCode;
+
+    pub fn JsPointerToString(stringValue: *const u16, stringLength: usize, value: *mut JsValueRef) -> JsErrorCode;
+
+    pub fn JsStringToPointer(
+        value: JsValueRef,
+        stringValue: *mut *const u16,
+        stringLength: *mut usize,
+    ) -> JsErrorCode;
+}
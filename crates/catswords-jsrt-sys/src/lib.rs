@@ -4,120 +4,534 @@
 
 use std::ffi::c_void;
 
+// Hand-maintained JsRT types. Function declarations live in `bindings.rs`
+// (checked in) or are generated from the ChakraCore headers with the
+// `bindgen` feature; both refer to the types below by name.
+
+pub type JsRef = *mut c_void;
 pub type JsRuntimeHandle = *mut c_void;
-pub type JsContextRef = *mut c_void;
-pub type JsValueRef = *mut c_void;
-pub type JsPropertyIdRef = *mut c_void;
+pub type JsContextRef = JsRef;
+pub type JsValueRef = JsRef;
+pub type JsPropertyIdRef = JsRef;
+pub type JsModuleRecord = JsRef;
+pub type JsWeakRef = JsRef;
+pub type JsSharedArrayBufferContentHandle = *mut c_void;
 pub type JsSourceContext = usize;
 
-#[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum JsErrorCode {
+pub const JS_INVALID_REFERENCE: JsRef = std::ptr::null_mut();
+pub const JS_INVALID_RUNTIME_HANDLE: JsRuntimeHandle = std::ptr::null_mut();
+pub const JS_SOURCE_CONTEXT_NONE: JsSourceContext = usize::MAX;
+
+// ChakraCore may hand back codes this crate does not know about yet, so the
+// error code is a plain integer newtype rather than a Rust enum.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JsErrorCode(pub u32);
+
+macro_rules! js_error_codes {
+    ($($name:ident = $value:expr,)*) => {
+        #[allow(non_upper_case_globals)]
+        impl JsErrorCode {
+            $(pub const $name: JsErrorCode = JsErrorCode($value);)*
+
+            pub fn name(self) -> Option<&'static str> {
+                match self.0 {
+                    $($value => Some(stringify!($name)),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+js_error_codes! {
     JsNoError = 0,
 
-    // Basic argument/context errors
+    // Usage
+    JsErrorCategoryUsage = 0x10000,
     JsErrorInvalidArgument = 0x10001,
     JsErrorNullArgument = 0x10002,
     JsErrorNoCurrentContext = 0x10003,
     JsErrorInExceptionState = 0x10004,
+    JsErrorNotImplemented = 0x10005,
+    JsErrorWrongThread = 0x10006,
+    JsErrorRuntimeInUse = 0x10007,
+    JsErrorBadSerializedScript = 0x10008,
+    JsErrorInDisabledState = 0x10009,
+    JsErrorCannotDisableExecution = 0x1000A,
+    JsErrorHeapEnumInProgress = 0x1000B,
+    JsErrorArgumentNotObject = 0x1000C,
+    JsErrorInProfileCallback = 0x1000D,
+    JsErrorInThreadServiceCallback = 0x1000E,
+    JsErrorCannotSerializeDebugScript = 0x1000F,
+    JsErrorAlreadyDebuggingContext = 0x10010,
+    JsErrorAlreadyProfilingContext = 0x10011,
+    JsErrorIdleNotEnabled = 0x10012,
+    JsCannotSetProjectionEnqueueCallback = 0x10013,
+    JsErrorCannotStartProjection = 0x10014,
+    JsErrorInObjectBeforeCollectCallback = 0x10015,
+    JsErrorObjectNotInspectable = 0x10016,
+    JsErrorPropertyNotSymbol = 0x10017,
+    JsErrorPropertyNotString = 0x10018,
+    JsErrorInvalidContext = 0x10019,
+    JsInvalidModuleHostInfoKind = 0x1001A,
+    JsErrorModuleParsed = 0x1001B,
+    JsNoWeakRefRequired = 0x1001C,
+    JsErrorPromisePending = 0x1001D,
+    JsErrorModuleNotEvaluated = 0x1001E,
+
+    // Engine
+    JsErrorCategoryEngine = 0x20000,
+    JsErrorOutOfMemory = 0x20001,
+    JsErrorBadFPUState = 0x20002,
 
-    // Script errors
+    // Script
+    JsErrorCategoryScript = 0x30000,
     JsErrorScriptException = 0x30001,
     JsErrorScriptCompile = 0x30002,
     JsErrorScriptTerminated = 0x30003,
     JsErrorScriptEvalDisabled = 0x30004,
 
     // Fatal
-    JsErrorFatal = 0x40000,
+    JsErrorCategoryFatal = 0x40000,
+    JsErrorFatal = 0x40001,
+    JsErrorWrongRuntime = 0x40002,
+
+    // Diagnostics (ChakraDebug.h)
+    JsErrorCategoryDiagError = 0x50000,
+    JsErrorDiagAlreadyInDebugMode = 0x50001,
+    JsErrorDiagNotInDebugMode = 0x50002,
+    JsErrorDiagNotAtBreak = 0x50003,
+    JsErrorDiagInvalidHandle = 0x50004,
+    JsErrorDiagObjectNotFound = 0x50005,
+    JsErrorDiagUnableToPerformAction = 0x50006,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JsErrorCategory {
+    NoError,
+    Usage,
+    Engine,
+    Script,
+    Fatal,
+    Diag,
+    Unknown,
+}
+
+impl JsErrorCode {
+    pub const fn is_ok(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn category(self) -> JsErrorCategory {
+        if self.0 == 0 {
+            return JsErrorCategory::NoError;
+        }
+        match self.0 & 0xFFFF_0000 {
+            0x10000 => JsErrorCategory::Usage,
+            0x20000 => JsErrorCategory::Engine,
+            0x30000 => JsErrorCategory::Script,
+            0x40000 => JsErrorCategory::Fatal,
+            0x50000 => JsErrorCategory::Diag,
+            _ => JsErrorCategory::Unknown,
+        }
+    }
+
+    pub const fn is_usage(self) -> bool {
+        matches!(self.category(), JsErrorCategory::Usage)
+    }
+
+    pub const fn is_engine(self) -> bool {
+        matches!(self.category(), JsErrorCategory::Engine)
+    }
+
+    pub const fn is_script(self) -> bool {
+        matches!(self.category(), JsErrorCategory::Script)
+    }
+
+    pub const fn is_fatal(self) -> bool {
+        matches!(self.category(), JsErrorCategory::Fatal)
+    }
+
+    pub const fn is_diag(self) -> bool {
+        matches!(self.category(), JsErrorCategory::Diag)
+    }
+}
+
+impl std::fmt::Debug for JsErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "JsErrorCode({:#x})", self.0),
+        }
+    }
+}
+
+// Enumerations the engine writes back through out-parameters or callback
+// arguments. Like `JsErrorCode` they are integer newtypes, so a value this
+// crate does not list is still representable instead of undefined behaviour.
+macro_rules! c_enum {
+    ($t:ident { $($name:ident = $value:expr,)* }) => {
+        #[repr(transparent)]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $t(pub u32);
+
+        #[allow(non_upper_case_globals)]
+        impl $t {
+            $(pub const $name: $t = $t($value);)*
+
+            pub fn name(self) -> Option<&'static str> {
+                match self.0 {
+                    $($value => Some(stringify!($name)),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl std::fmt::Debug for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.name() {
+                    Some(name) => f.write_str(name),
+                    None => write!(f, concat!(stringify!($t), "({})"), self.0),
+                }
+            }
+        }
+    };
+}
+
+c_enum! {
+    JsValueType {
+        JsUndefined = 0,
+        JsNull = 1,
+        JsNumber = 2,
+        JsString = 3,
+        JsBoolean = 4,
+        JsObject = 5,
+        JsFunction = 6,
+        JsError = 7,
+        JsArray = 8,
+        JsSymbol = 9,
+        JsArrayBuffer = 10,
+        JsTypedArray = 11,
+        JsDataView = 12,
+    }
+}
+
+// Bit operations for the flag-set newtypes below.
+macro_rules! flag_ops {
+    ($($t:ident),*) => {$(
+        impl $t {
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl std::ops::BitOr for $t {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $t {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl std::ops::BitAnd for $t {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl std::ops::Not for $t {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                Self(!self.0)
+            }
+        }
+    )*};
+}
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JsRuntimeAttributes(pub u32);
+
+#[allow(non_upper_case_globals)]
+impl JsRuntimeAttributes {
+    pub const JsRuntimeAttributeNone: Self = Self(0x0);
+    pub const JsRuntimeAttributeDisableBackgroundWork: Self = Self(0x1);
+    pub const JsRuntimeAttributeAllowScriptInterrupt: Self = Self(0x2);
+    pub const JsRuntimeAttributeEnableIdleProcessing: Self = Self(0x4);
+    pub const JsRuntimeAttributeDisableNativeCodeGeneration: Self = Self(0x8);
+    pub const JsRuntimeAttributeDisableEval: Self = Self(0x10);
+    pub const JsRuntimeAttributeEnableExperimentalFeatures: Self = Self(0x20);
+    pub const JsRuntimeAttributeDispatchSetExceptionsToDebugger: Self = Self(0x40);
+    pub const JsRuntimeAttributeDisableFatalOnOOM: Self = Self(0x80);
+    pub const JsRuntimeAttributeDisableExecutablePageAllocation: Self = Self(0x100);
+}
+
+c_enum! {
+    JsTypedArrayType {
+        JsArrayTypeInt8 = 0,
+        JsArrayTypeUint8 = 1,
+        JsArrayTypeUint8Clamped = 2,
+        JsArrayTypeInt16 = 3,
+        JsArrayTypeUint16 = 4,
+        JsArrayTypeInt32 = 5,
+        JsArrayTypeUint32 = 6,
+        JsArrayTypeFloat32 = 7,
+        JsArrayTypeFloat64 = 8,
+    }
+}
+
+c_enum! {
+    JsMemoryEventType {
+        JsMemoryAllocate = 0,
+        JsMemoryFree = 1,
+        JsMemoryFailure = 2,
+    }
+}
+
+c_enum! {
+    JsPropertyIdType {
+        JsPropertyIdTypeString = 0,
+        JsPropertyIdTypeSymbol = 1,
+    }
+}
+
+c_enum! {
+    JsPromiseState {
+        JsPromiseStatePending = 0,
+        JsPromiseStateFulfilled = 1,
+        JsPromiseStateRejected = 2,
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsModuleHostInfoKind {
+    JsModuleHostInfo_Exception = 0x01,
+    JsModuleHostInfo_HostDefined = 0x02,
+    JsModuleHostInfo_NotifyModuleReadyCallback = 0x3,
+    JsModuleHostInfo_FetchImportedModuleCallback = 0x4,
+    JsModuleHostInfo_FetchImportedModuleFromScriptCallback = 0x5,
+    JsModuleHostInfo_Url = 0x6,
+    JsModuleHostInfo_InitializeImportMetaCallback = 0x7,
+    JsModuleHostInfo_ReportModuleCompletionCallback = 0x8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsParseModuleSourceFlags {
+    JsParseModuleSourceFlags_DataIsUTF16LE = 0x0,
+    JsParseModuleSourceFlags_DataIsUTF8 = 0x1,
+}
+
+// Flag set; kept as an integer newtype so combinations stay representable.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JsParseScriptAttributes(pub u32);
+
+#[allow(non_upper_case_globals)]
+impl JsParseScriptAttributes {
+    pub const JsParseScriptAttributeNone: Self = Self(0x0);
+    pub const JsParseScriptAttributeLibraryCode: Self = Self(0x1);
+    pub const JsParseScriptAttributeArrayBufferIsUtf16Encoded: Self = Self(0x2);
+    pub const JsParseScriptAttributeStrictMode: Self = Self(0x4);
+}
+
+flag_ops!(JsRuntimeAttributes, JsParseScriptAttributes);
+
+// ChakraDebug.h
+
+c_enum! {
+    JsDiagDebugEvent {
+        JsDiagDebugEventSourceCompile = 0,
+        JsDiagDebugEventCompileError = 1,
+        JsDiagDebugEventBreakpoint = 2,
+        JsDiagDebugEventStepComplete = 3,
+        JsDiagDebugEventDebuggerStatement = 4,
+        JsDiagDebugEventAsyncBreak = 5,
+        JsDiagDebugEventRuntimeException = 6,
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JsDiagBreakOnExceptionAttributes(pub u32);
+
+#[allow(non_upper_case_globals)]
+impl JsDiagBreakOnExceptionAttributes {
+    pub const JsDiagBreakOnExceptionAttributeNone: Self = Self(0x0);
+    pub const JsDiagBreakOnExceptionAttributeUncaught: Self = Self(0x1);
+    pub const JsDiagBreakOnExceptionAttributeFirstChance: Self = Self(0x2);
+}
+
+flag_ops!(JsDiagBreakOnExceptionAttributes);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsDiagStepType {
+    JsDiagStepTypeStepIn = 0,
+    JsDiagStepTypeStepOut = 1,
+    JsDiagStepTypeStepOver = 2,
+    JsDiagStepTypeStepBack = 3,
+    JsDiagStepTypeContinue = 4,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
-pub enum JsRuntimeAttributes {
-    None = 0,
+pub struct JsNativeFunctionInfo {
+    pub thisArg: JsValueRef,
+    pub newTargetArg: JsValueRef,
+    pub isConstructCall: bool,
 }
 
+// Callbacks
+
 pub type JsNativeFunction = Option<
     unsafe extern "C" fn(
         callee: JsValueRef,
         is_construct_call: bool,
-        arguments: *const JsValueRef,
+        arguments: *mut JsValueRef,
         argument_count: u16,
         callback_state: *mut c_void,
     ) -> JsValueRef
 >;
 
-extern "C" {
-    pub fn JsCreateRuntime(
-        attributes: JsRuntimeAttributes,
-        thread_service: *mut c_void,
-        runtime: *mut JsRuntimeHandle,
-    ) -> JsErrorCode;
+pub type JsEnhancedNativeFunction = Option<
+    unsafe extern "C" fn(
+        callee: JsValueRef,
+        arguments: *mut JsValueRef,
+        argument_count: u16,
+        info: *mut JsNativeFunctionInfo,
+        callback_state: *mut c_void,
+    ) -> JsValueRef
+>;
 
-    pub fn JsDisposeRuntime(runtime: JsRuntimeHandle) -> JsErrorCode;
+pub type JsBackgroundWorkItemCallback = Option<unsafe extern "C" fn(callback_state: *mut c_void)>;
 
-    pub fn JsCreateContext(
-        runtime: JsRuntimeHandle,
-        new_context: *mut JsContextRef,
-    ) -> JsErrorCode;
+pub type JsThreadServiceCallback = Option<
+    unsafe extern "C" fn(callback: JsBackgroundWorkItemCallback, callback_state: *mut c_void) -> bool
+>;
 
-    pub fn JsSetCurrentContext(context: JsContextRef) -> JsErrorCode;
-    pub fn JsGetCurrentContext(current_context: *mut JsContextRef) -> JsErrorCode;
+pub type JsMemoryAllocationCallback = Option<
+    unsafe extern "C" fn(
+        callback_state: *mut c_void,
+        allocation_event: JsMemoryEventType,
+        allocation_size: usize,
+    ) -> bool
+>;
 
-    pub fn JsRunScript(
-        script: *const u16,
-        source_context: JsSourceContext,
-        source_url: *const u16,
-        result: *mut JsValueRef,
-    ) -> JsErrorCode;
+pub type JsBeforeCollectCallback = Option<unsafe extern "C" fn(callback_state: *mut c_void)>;
 
-    pub fn JsIntToNumber(value: i32, result: *mut JsValueRef) -> JsErrorCode;
-    pub fn JsNumberToInt(value: JsValueRef, result: *mut i32) -> JsErrorCode;
+pub type JsObjectBeforeCollectCallback = Option<unsafe extern "C" fn(reference: JsRef, callback_state: *mut c_void)>;
 
-    pub fn JsCreateFunction(
-        native_function: JsNativeFunction,
-        callback_state: *mut c_void,
-        function: *mut JsValueRef,
-    ) -> JsErrorCode;
+pub type JsFinalizeCallback = Option<unsafe extern "C" fn(data: *mut c_void)>;
 
-    pub fn JsCallFunction(
-        function: JsValueRef,
-        arguments: *const JsValueRef,
-        argument_count: u16,
-        result: *mut JsValueRef,
-    ) -> JsErrorCode;
-	
-    pub fn JsCreateString(
-        content: *const u8,
-        length: usize,
+pub type JsPromiseContinuationCallback = Option<unsafe extern "C" fn(task: JsValueRef, callback_state: *mut c_void)>;
+
+pub type JsHostPromiseRejectionTrackerCallback = Option<
+    unsafe extern "C" fn(promise: JsValueRef, reason: JsValueRef, handled: bool, callback_state: *mut c_void)
+>;
+
+pub type JsSerializedLoadScriptCallback = Option<
+    unsafe extern "C" fn(
+        source_context: JsSourceContext,
         value: *mut JsValueRef,
-    ) -> JsErrorCode;
+        parse_attributes: *mut JsParseScriptAttributes,
+    ) -> bool
+>;
+
+#[cfg(windows)]
+pub type JsSerializedScriptLoadSourceCallback = Option<
+    unsafe extern "C" fn(source_context: JsSourceContext, script_buffer: *mut *const u16) -> bool
+>;
 
-    pub fn JsCreateError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+#[cfg(windows)]
+pub type JsSerializedScriptUnloadCallback = Option<unsafe extern "C" fn(source_context: JsSourceContext)>;
 
-    pub fn JsCreateTypeError(message: JsValueRef, error: *mut JsValueRef) -> JsErrorCode;
+pub type FetchImportedModuleCallBack = Option<
+    unsafe extern "C" fn(
+        referencing_module: JsModuleRecord,
+        specifier: JsValueRef,
+        dependent_module_record: *mut JsModuleRecord,
+    ) -> JsErrorCode
+>;
 
-    pub fn JsAddRef(reference: JsValueRef, count: *mut u32) -> JsErrorCode;
-    pub fn JsRelease(reference: JsValueRef, count: *mut u32) -> JsErrorCode;
+pub type FetchImportedModuleFromScriptCallBack = Option<
+    unsafe extern "C" fn(
+        referencing_source_context: JsSourceContext,
+        specifier: JsValueRef,
+        dependent_module_record: *mut JsModuleRecord,
+    ) -> JsErrorCode
+>;
+
+pub type NotifyModuleReadyCallback = Option<
+    unsafe extern "C" fn(referencing_module: JsModuleRecord, exception_var: JsValueRef) -> JsErrorCode
+>;
+
+pub type InitializeImportMetaCallback = Option<
+    unsafe extern "C" fn(referencing_module: JsModuleRecord, import_meta_var: JsValueRef) -> JsErrorCode
+>;
+
+pub type ReportModuleCompletionCallback = Option<
+    unsafe extern "C" fn(module: JsModuleRecord, exception: JsValueRef) -> JsErrorCode
+>;
+
+pub type JsDiagDebugEventCallback = Option<
+    unsafe extern "C" fn(debug_event: JsDiagDebugEvent, event_data: JsValueRef, callback_state: *mut c_void)
+>;
 
-    pub fn JsGetGlobalObject(global_object: *mut JsValueRef) -> JsErrorCode;
+#[cfg(not(feature = "bindgen"))]
+include!("bindings.rs");
 
-    pub fn JsGetPropertyIdFromName(name: *const u16, property_id: *mut JsPropertyIdRef)
-        -> JsErrorCode;
+#[cfg(feature = "bindgen")]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
-    pub fn JsGetProperty(object: JsValueRef, property_id: JsPropertyIdRef, value: *mut JsValueRef)
-        -> JsErrorCode;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    pub fn JsSetProperty(object: JsValueRef, property_id: JsPropertyIdRef, value: JsValueRef, use_strict: bool)
-        -> JsErrorCode;
+    #[test]
+    fn error_category_boundaries() {
+        assert_eq!(JsErrorCode::JsNoError.category(), JsErrorCategory::NoError);
+        assert_eq!(JsErrorCode(0xFFFF).category(), JsErrorCategory::Unknown);
+        assert_eq!(JsErrorCode(0x10000).category(), JsErrorCategory::Usage);
+        assert_eq!(JsErrorCode(0x1FFFF).category(), JsErrorCategory::Usage);
+        assert_eq!(JsErrorCode(0x20000).category(), JsErrorCategory::Engine);
+        assert_eq!(JsErrorCode(0x30000).category(), JsErrorCategory::Script);
+        assert_eq!(JsErrorCode(0x40000).category(), JsErrorCategory::Fatal);
+        assert_eq!(JsErrorCode(0x50006).category(), JsErrorCategory::Diag);
+        assert_eq!(JsErrorCode(0x60000).category(), JsErrorCategory::Unknown);
 
-    pub fn JsCreateObject(object: *mut JsValueRef) -> JsErrorCode;
+        assert!(JsErrorCode::JsErrorWrongThread.is_usage());
+        assert!(JsErrorCode::JsErrorOutOfMemory.is_engine());
+        assert!(JsErrorCode::JsErrorScriptException.is_script());
+        assert!(JsErrorCode::JsErrorWrongRuntime.is_fatal());
+    }
 
-    pub fn JsDeleteProperty(object: JsValueRef, property_id: JsPropertyIdRef, use_strict: bool, result: *mut JsValueRef)
-        -> JsErrorCode;
+    #[test]
+    fn error_names() {
+        assert_eq!(JsErrorCode::JsErrorCategoryUsage.name(), Some("JsErrorCategoryUsage"));
+        assert_eq!(JsErrorCode(0x10001).name(), Some("JsErrorInvalidArgument"));
+        assert_eq!(JsErrorCode(0x30002).name(), Some("JsErrorScriptCompile"));
+        assert_eq!(JsErrorCode(0x1FFFF).name(), None);
+        assert_eq!(format!("{:?}", JsErrorCode(0x1FFFF)), "JsErrorCode(0x1ffff)");
+        assert_eq!(format!("{:?}", JsErrorCode::JsErrorFatal), "JsErrorFatal");
+    }
 
-    // Recommended additions for better ergonomics / correctness
-    pub fn JsGetUndefinedValue(undefined_value: *mut JsValueRef) -> JsErrorCode;
-    pub fn JsGetNullValue(null_value: *mut JsValueRef) -> JsErrorCode;
-    pub fn JsSetException(exception: JsValueRef) -> JsErrorCode;
+    #[test]
+    fn unknown_enum_values_stay_representable() {
+        assert_eq!(JsValueType(12), JsValueType::JsDataView);
+        assert_eq!(JsValueType(99).name(), None);
+        assert_eq!(format!("{:?}", JsValueType(99)), "JsValueType(99)");
+        assert_eq!(format!("{:?}", JsMemoryEventType::JsMemoryFailure), "JsMemoryFailure");
+    }
 }
@@ -0,0 +1,242 @@
+// Checks the checked-in src/bindings.rs against declarations freshly generated
+// from the ChakraCore headers. Like the `bindgen` feature itself this needs
+// libclang. Run with:
+//
+//     cargo test -p catswords-jsrt-sys --features bindgen
+//
+// `functions!` takes the one list of checked functions. It places each pair in
+// one array, which only type-checks when both coerce to the same
+// `unsafe extern "C" fn` pointer type, and keeps the names for the coverage tests.
+#![cfg(feature = "bindgen")]
+#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals, dead_code)]
+
+mod generated {
+    use catswords_jsrt_sys::*;
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+mod pregenerated {
+    use catswords_jsrt_sys::*;
+    include!("../src/bindings.rs");
+}
+
+macro_rules! functions {
+    (common: [$($common:ident),* $(,)?] windows: [$($windows:ident),* $(,)?]) => {
+        const COMMON: &[&str] = &[$(stringify!($common)),*];
+        const WINDOWS: &[&str] = &[$(stringify!($windows)),*];
+
+        #[test]
+        fn signatures_match_headers() {
+            $( let _ = [pregenerated::$common, generated::$common]; )*
+            #[cfg(windows)]
+            {
+                $( let _ = [pregenerated::$windows, generated::$windows]; )*
+            }
+        }
+    };
+}
+
+functions! {
+    common: [
+        JsCreateRuntime,
+        JsCollectGarbage,
+        JsDisposeRuntime,
+        JsGetRuntimeMemoryUsage,
+        JsGetRuntimeMemoryLimit,
+        JsSetRuntimeMemoryLimit,
+        JsSetRuntimeMemoryAllocationCallback,
+        JsSetRuntimeBeforeCollectCallback,
+        JsAddRef,
+        JsRelease,
+        JsSetObjectBeforeCollectCallback,
+        JsCreateContext,
+        JsGetCurrentContext,
+        JsSetCurrentContext,
+        JsGetContextOfObject,
+        JsGetContextData,
+        JsSetContextData,
+        JsGetRuntime,
+        JsIdle,
+        JsGetSymbolFromPropertyId,
+        JsGetPropertyIdType,
+        JsGetPropertyIdFromSymbol,
+        JsCreateSymbol,
+        JsGetOwnPropertySymbols,
+        JsGetUndefinedValue,
+        JsGetNullValue,
+        JsGetTrueValue,
+        JsGetFalseValue,
+        JsBoolToBoolean,
+        JsBooleanToBool,
+        JsConvertValueToBoolean,
+        JsGetValueType,
+        JsDoubleToNumber,
+        JsIntToNumber,
+        JsNumberToDouble,
+        JsNumberToInt,
+        JsConvertValueToNumber,
+        JsGetStringLength,
+        JsConvertValueToString,
+        JsGetGlobalObject,
+        JsCreateObject,
+        JsCreateExternalObject,
+        JsConvertValueToObject,
+        JsGetPrototype,
+        JsSetPrototype,
+        JsInstanceOf,
+        JsGetExtensionAllowed,
+        JsPreventExtension,
+        JsGetProperty,
+        JsGetOwnPropertyDescriptor,
+        JsGetOwnPropertyNames,
+        JsSetProperty,
+        JsHasProperty,
+        JsDeleteProperty,
+        JsDefineProperty,
+        JsHasIndexedProperty,
+        JsGetIndexedProperty,
+        JsSetIndexedProperty,
+        JsDeleteIndexedProperty,
+        JsHasIndexedPropertiesExternalData,
+        JsGetIndexedPropertiesExternalData,
+        JsSetIndexedPropertiesToExternalData,
+        JsEquals,
+        JsStrictEquals,
+        JsHasExternalData,
+        JsGetExternalData,
+        JsSetExternalData,
+        JsCreateArray,
+        JsCreateArrayBuffer,
+        JsCreateExternalArrayBuffer,
+        JsCreateTypedArray,
+        JsCreateDataView,
+        JsGetTypedArrayInfo,
+        JsGetArrayBufferStorage,
+        JsGetTypedArrayStorage,
+        JsGetDataViewStorage,
+        JsDetachArrayBuffer,
+        JsCallFunction,
+        JsConstructObject,
+        JsCreateFunction,
+        JsCreateNamedFunction,
+        JsCreateError,
+        JsCreateRangeError,
+        JsCreateReferenceError,
+        JsCreateSyntaxError,
+        JsCreateTypeError,
+        JsCreateURIError,
+        JsHasException,
+        JsGetAndClearException,
+        JsSetException,
+        JsDisableRuntimeExecution,
+        JsEnableRuntimeExecution,
+        JsIsRuntimeExecutionDisabled,
+        JsSetPromiseContinuationCallback,
+        JsInitializeModuleRecord,
+        JsParseModuleSource,
+        JsModuleEvaluation,
+        JsSetModuleHostInfo,
+        JsGetModuleHostInfo,
+        JsGetAndClearExceptionWithMetadata,
+        JsGetModuleNamespace,
+        JsCreateEnhancedFunction,
+        JsCreateString,
+        JsCreateStringUtf16,
+        JsCopyString,
+        JsCopyStringUtf16,
+        JsCopyStringOneByte,
+        JsParse,
+        JsRun,
+        JsCreatePropertyId,
+        JsCopyPropertyId,
+        JsSerialize,
+        JsParseSerialized,
+        JsRunSerialized,
+        JsGetPromiseState,
+        JsGetPromiseResult,
+        JsCreatePromise,
+        JsCreateWeakReference,
+        JsGetWeakReferenceValue,
+        JsCreateSharedArrayBufferWithSharedContent,
+        JsGetSharedArrayBufferContent,
+        JsReleaseSharedArrayBufferContentHandle,
+        JsHasOwnProperty,
+        JsGetDataViewInfo,
+        JsLessThan,
+        JsLessThanOrEqual,
+        JsCreateExternalObjectWithPrototype,
+        JsObjectGetProperty,
+        JsObjectSetProperty,
+        JsObjectHasProperty,
+        JsObjectDefineProperty,
+        JsObjectDeleteProperty,
+        JsObjectGetOwnPropertyDescriptor,
+        JsObjectHasOwnProperty,
+        JsSetHostPromiseRejectionTracker,
+        JsGetProxyProperties,
+        JsDiagStartDebugging,
+        JsDiagStopDebugging,
+        JsDiagRequestAsyncBreak,
+        JsDiagGetBreakpoints,
+        JsDiagSetBreakpoint,
+        JsDiagRemoveBreakpoint,
+        JsDiagSetBreakOnException,
+        JsDiagGetBreakOnException,
+        JsDiagSetStepType,
+        JsDiagGetScripts,
+        JsDiagGetSource,
+        JsDiagGetFunctionPosition,
+        JsDiagGetStackTrace,
+        JsDiagGetStackProperties,
+        JsDiagGetProperties,
+        JsDiagGetObjectFromHandle,
+        JsDiagEvaluate,
+    ]
+    windows: [
+        JsParseScript,
+        JsParseScriptWithAttributes,
+        JsRunScript,
+        JsExperimentalApiRunModule,
+        JsSerializeScript,
+        JsParseSerializedScriptWithCallback,
+        JsRunSerializedScriptWithCallback,
+        JsParseSerializedScript,
+        JsRunSerializedScript,
+        JsGetPropertyIdFromName,
+        JsGetPropertyNameFromId,
+        JsPointerToString,
+        JsStringToPointer,
+    ]
+}
+
+fn declared_functions(source: &str) -> Vec<&str> {
+    source
+        .split("pub fn ")
+        .skip(1)
+        .filter_map(|rest| rest.split(|c: char| !(c.is_alphanumeric() || c == '_')).next())
+        .collect()
+}
+
+#[test]
+fn every_checked_in_function_is_verified() {
+    let source = include_str!("../src/bindings.rs");
+    for name in declared_functions(source) {
+        assert!(
+            COMMON.contains(&name) || WINDOWS.contains(&name),
+            "{} is declared in src/bindings.rs but not checked against the headers",
+            name
+        );
+    }
+}
+
+#[test]
+fn headers_are_fully_covered() {
+    let source = include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+    for name in declared_functions(source) {
+        assert!(
+            COMMON.contains(&name) || WINDOWS.contains(&name),
+            "{} is exported by the ChakraCore headers but missing from src/bindings.rs",
+            name
+        );
+    }
+}
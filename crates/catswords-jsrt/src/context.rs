@@ -1,64 +1,75 @@
 use crate::error::{ok, ok_msg, Result};
 use crate::guard::Guard;
 use crate::runtime::Runtime;
-use crate::value::Value;
+use crate::value::{Class, JsMethods, PropertyKey, Value};
 use catswords_jsrt_sys as sys;
 
-fn to_wide(s: &str) -> Vec<u16> {
-    s.encode_utf16().chain(std::iter::once(0)).collect()
-}
-
 pub struct Context<'rt> {
     pub(crate) raw: sys::JsContextRef,
     runtime: &'rt Runtime,
+    _not_send: std::marker::PhantomData<*mut ()>,
 }
 
 impl<'rt> Context<'rt> {
     pub fn new(runtime: &'rt Runtime) -> Result<Self> {
+        runtime.check_thread()?;
         let mut cx: sys::JsContextRef = std::ptr::null_mut();
-        unsafe { ok(sys::JsCreateContext(runtime.raw, &mut cx))?; }
-        Ok(Self { raw: cx, runtime })
+        unsafe { ok(sys::JsCreateContext(runtime.raw(), &mut cx))?; }
+        Ok(Self { raw: cx, runtime, _not_send: std::marker::PhantomData })
     }
 
+    // Fails with JsErrorWrongRuntime while a guard of another runtime is live on
+    // this thread.
     pub fn make_current(&self) -> Result<Guard<'rt>> {
+        self.runtime.check_thread()?;
         let mut prev: sys::JsContextRef = std::ptr::null_mut();
         unsafe {
             ok(sys::JsGetCurrentContext(&mut prev))?;
-            ok(sys::JsSetCurrentContext(self.raw))?;
         }
-        Ok(Guard {
+        Guard::enter(self.runtime)?;
+        let guard = Guard {
             prev,
             current: self.raw,
             runtime: self.runtime,
+            entered: true,
             _marker: std::marker::PhantomData,
-        })
+        };
+        unsafe {
+            ok(sys::JsSetCurrentContext(self.raw))?;
+        }
+        self.runtime.drop_deferred();
+        Ok(guard)
     }
 
-    pub fn set_global(&self, name: &str, value: &Value) -> Result<()> {
+    pub fn set_global(&self, name: &str, value: &Value<'_>) -> Result<()> {
+        // Enter this context so the global object read below is ours.
+        let guard = self.make_current()?;
         let mut global: sys::JsValueRef = std::ptr::null_mut();
         unsafe { ok_msg(sys::JsGetGlobalObject(&mut global), "JsGetGlobalObject failed")?; }
 
-        let w = to_wide(name);
-        let mut pid: sys::JsPropertyIdRef = std::ptr::null_mut();
+        let pid = name.property_id(&guard)?;
         unsafe {
             ok_msg(
-                sys::JsGetPropertyIdFromName(w.as_ptr(), &mut pid),
-                "JsGetPropertyIdFromName failed",
-            )?;
-            ok_msg(
-                sys::JsSetProperty(global, pid, value.raw(), true),
+                sys::JsSetProperty(global, pid.raw(), value.raw(), true),
                 "JsSetProperty failed",
             )?;
         }
         Ok(())
     }
+
+    // Define the class `T` and store its constructor as the global `T::NAME`.
+    pub fn register_class<T: JsMethods>(&self) -> Result<()> {
+        let guard = self.make_current()?;
+        let constructor = Class::<T>::define(&guard)?;
+        self.set_global(T::NAME, constructor.as_value())
+    }
     
     #[deprecated(note = "use set_global(name, value) instead")]
-    pub fn old_set_global(&self, _guard: &Guard<'_>, name: &str, value: &Value) -> Result<()> {
+    pub fn old_set_global(&self, _guard: &Guard<'_>, name: &str, value: &Value<'_>) -> Result<()> {
         self.set_global(name, value)
     }
 
     pub(crate) fn from_raw(rt: &'rt Runtime, raw: sys::JsContextRef) -> Self {
-        Self { raw, runtime: rt }
+        Self { raw, runtime: rt, _not_send: std::marker::PhantomData }
     }
 }
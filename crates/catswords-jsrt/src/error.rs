@@ -1,12 +1,76 @@
-use catswords_jsrt_sys::JsErrorCode;
+use crate::exception::{self, JsException};
+use catswords_jsrt_sys::{JsErrorCategory, JsErrorCode};
 use std::borrow::Cow;
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
-#[error("{message} (code={code:?})")]
-pub struct Error {
-    pub code: JsErrorCode,
-    pub message: Cow<'static, str>,
+pub enum Error {
+    #[error("{message} (code={code:?})")]
+    Jsrt {
+        code: JsErrorCode,
+        message: Cow<'static, str>,
+    },
+
+    // Script threw (or failed to compile); the exception has been cleared from the engine.
+    #[error("{0}")]
+    JsException(Box<JsException>),
+
+    // A value had the wrong JavaScript type for the requested conversion.
+    // Thrown back into script as a TypeError.
+    #[error("TypeError: {0}")]
+    Type(Cow<'static, str>),
+
+    // Execution was stopped through an InterruptHandle (or a timeout).
+    #[error("script execution was terminated")]
+    ScriptTerminated,
+
+    // The runtime hit its memory limit (or the OS refused memory). The runtime
+    // stays usable; drop references or raise the limit and retry.
+    #[error("out of memory")]
+    OutOfMemory,
+}
+
+impl Error {
+    pub fn code(&self) -> JsErrorCode {
+        match self {
+            Error::Jsrt { code, .. } => *code,
+            Error::JsException(e) => e.code,
+            Error::Type(_) => JsErrorCode::JsErrorInvalidArgument,
+            Error::ScriptTerminated => JsErrorCode::JsErrorScriptTerminated,
+            Error::OutOfMemory => JsErrorCode::JsErrorOutOfMemory,
+        }
+    }
+
+    pub fn category(&self) -> JsErrorCategory {
+        self.code().category()
+    }
+
+    pub fn is_usage(&self) -> bool {
+        self.code().is_usage()
+    }
+
+    pub fn is_engine(&self) -> bool {
+        self.code().is_engine()
+    }
+
+    pub fn is_script(&self) -> bool {
+        self.code().is_script()
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        self.code().is_fatal()
+    }
+
+    pub fn is_diag(&self) -> bool {
+        self.code().is_diag()
+    }
+
+    pub fn exception(&self) -> Option<&JsException> {
+        match self {
+            Error::JsException(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -19,16 +83,35 @@ pub(crate) fn ok(code: JsErrorCode) -> Result<()> {
 #[inline]
 pub(crate) fn ok_msg(code: JsErrorCode, msg: &'static str) -> Result<()> {
     if code == JsErrorCode::JsNoError {
-        Ok(())
-    } else {
-        Err(Error {
-            code,
-            message: Cow::Borrowed(msg),
-        })
+        return Ok(());
+    }
+
+    if code == JsErrorCode::JsErrorScriptTerminated {
+        return Err(Error::ScriptTerminated);
+    }
+
+    if code == JsErrorCode::JsErrorOutOfMemory {
+        return Err(Error::OutOfMemory);
+    }
+
+    if matches!(code, JsErrorCode::JsErrorScriptException | JsErrorCode::JsErrorScriptCompile) {
+        if let Some(e) = exception::take_pending(code) {
+            return Err(e);
+        }
     }
+
+    Err(Error::Jsrt {
+        code,
+        message: Cow::Borrowed(msg),
+    })
 }
 
 #[inline]
 pub fn err_msg(code: JsErrorCode, msg: String) -> Error {
-    Error { code, message: Cow::Owned(msg) }
-}
\ No newline at end of file
+    Error::Jsrt { code, message: Cow::Owned(msg) }
+}
+
+#[inline]
+pub fn type_err(msg: String) -> Error {
+    Error::Type(Cow::Owned(msg))
+}
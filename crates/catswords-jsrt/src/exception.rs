@@ -0,0 +1,194 @@
+use crate::error::{err_msg, Error, Result};
+use crate::guard::Guard;
+use crate::runtime;
+use crate::value::{PersistentValue, Value};
+use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::JsErrorCode;
+use std::ffi::c_char;
+use std::fmt;
+use std::sync::Arc;
+
+/// A JavaScript exception taken out of the engine after a failed call.
+///
+/// `line` and `column` are zero-based, as reported by ChakraCore.
+#[derive(Debug, Clone)]
+pub struct JsException {
+    pub code: JsErrorCode,
+    pub message: String,
+    pub name: Option<String>,
+    pub stack: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub source: Option<String>,
+    pub url: Option<String>,
+    thrown: Thrown,
+}
+
+// The thrown value, rooted for as long as any clone of the exception lives. It is
+// only read back under a guard of the runtime that threw it, and released only
+// on that runtime's thread (or leaked), so `Error` stays Send + Sync.
+#[derive(Clone)]
+struct Thrown(Option<Arc<PersistentValue>>);
+
+unsafe impl Send for Thrown {}
+unsafe impl Sync for Thrown {}
+
+impl fmt::Debug for Thrown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Thrown(..)" } else { "Thrown(None)" })
+    }
+}
+
+impl JsException {
+    /// The thrown value itself. Fails with `JsErrorWrongRuntime` under a guard
+    /// of another runtime, or if the engine refused to keep the value alive.
+    pub fn value<'g>(&self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        match &self.thrown.0 {
+            Some(value) => value.get(guard),
+            None => Err(err_msg(self.code, "the thrown value was not kept".to_string())),
+        }
+    }
+}
+
+impl fmt::Display for JsException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}: {}", name, self.message)?,
+            None => write!(f, "Uncaught {}", self.message)?,
+        }
+        if let (Some(url), Some(line), Some(column)) = (&self.url, self.line, self.column) {
+            write!(f, " ({}:{}:{})", url, line + 1, column + 1)?;
+        }
+        Ok(())
+    }
+}
+
+// Clear whatever a nested helper call may have thrown, so that reading the
+// exception never leaves the engine in exception state.
+pub(crate) unsafe fn discard_pending() {
+    let mut has = false;
+    if sys::JsHasException(&mut has) == JsErrorCode::JsNoError && has {
+        let mut ignored: sys::JsValueRef = std::ptr::null_mut();
+        let _ = sys::JsGetAndClearException(&mut ignored);
+    }
+}
+
+unsafe fn value_type(v: sys::JsValueRef) -> Option<sys::JsValueType> {
+    let mut t = sys::JsValueType::JsUndefined;
+    if sys::JsGetValueType(v, &mut t) == JsErrorCode::JsNoError {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+unsafe fn get_property(obj: sys::JsValueRef, name: &str) -> Option<sys::JsValueRef> {
+    let mut pid: sys::JsPropertyIdRef = std::ptr::null_mut();
+    if sys::JsCreatePropertyId(name.as_ptr() as *const c_char, name.len(), &mut pid) != JsErrorCode::JsNoError {
+        return None;
+    }
+    let mut out: sys::JsValueRef = std::ptr::null_mut();
+    if sys::JsGetProperty(obj, pid, &mut out) != JsErrorCode::JsNoError {
+        discard_pending();
+        return None;
+    }
+    match value_type(out) {
+        None | Some(sys::JsValueType::JsUndefined) | Some(sys::JsValueType::JsNull) => None,
+        Some(_) => Some(out),
+    }
+}
+
+unsafe fn to_rust_string(v: sys::JsValueRef) -> Option<String> {
+    let mut s: sys::JsValueRef = std::ptr::null_mut();
+    if sys::JsConvertValueToString(v, &mut s) != JsErrorCode::JsNoError {
+        discard_pending();
+        return None;
+    }
+
+    let mut len: usize = 0;
+    if sys::JsCopyString(s, std::ptr::null_mut(), 0, &mut len) != JsErrorCode::JsNoError {
+        return None;
+    }
+    let mut buf: Vec<u8> = vec![0; len];
+    if sys::JsCopyString(s, buf.as_mut_ptr() as *mut _, len, &mut len) != JsErrorCode::JsNoError {
+        return None;
+    }
+    buf.truncate(len);
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+unsafe fn to_u32(v: sys::JsValueRef) -> Option<u32> {
+    let mut out: i32 = 0;
+    if sys::JsNumberToInt(v, &mut out) != JsErrorCode::JsNoError {
+        return None;
+    }
+    u32::try_from(out).ok()
+}
+
+// Take the pending exception out of the engine and describe it.
+// Returns None when the engine has no exception to hand over.
+pub(crate) fn take_pending(code: JsErrorCode) -> Option<Error> {
+    unsafe {
+        let mut exception: sys::JsValueRef = std::ptr::null_mut();
+        let mut metadata: sys::JsValueRef = std::ptr::null_mut();
+
+        if sys::JsGetAndClearExceptionWithMetadata(&mut metadata) == JsErrorCode::JsNoError {
+            exception = get_property(metadata, "exception").unwrap_or(std::ptr::null_mut());
+        } else {
+            metadata = std::ptr::null_mut();
+            if sys::JsGetAndClearException(&mut exception) != JsErrorCode::JsNoError {
+                return None;
+            }
+        }
+
+        if exception.is_null() {
+            let mut undef: sys::JsValueRef = std::ptr::null_mut();
+            let _ = sys::JsGetUndefinedValue(&mut undef);
+            exception = undef;
+        }
+
+        let is_object = matches!(
+            value_type(exception),
+            Some(sys::JsValueType::JsObject) | Some(sys::JsValueType::JsError)
+        );
+
+        let (name, message, stack) = if is_object {
+            let name = get_property(exception, "name").and_then(|v| to_rust_string(v));
+            let message = get_property(exception, "message").and_then(|v| to_rust_string(v));
+            let stack = get_property(exception, "stack").and_then(|v| to_rust_string(v));
+            let message = message
+                .or_else(|| to_rust_string(exception))
+                .unwrap_or_default();
+            (name, message, stack)
+        } else {
+            (None, to_rust_string(exception).unwrap_or_default(), None)
+        };
+
+        let (line, column, source, url) = if metadata.is_null() {
+            (None, None, None, None)
+        } else {
+            (
+                get_property(metadata, "line").and_then(|v| to_u32(v)),
+                get_property(metadata, "column").and_then(|v| to_u32(v)),
+                get_property(metadata, "source").and_then(|v| to_rust_string(v)),
+                get_property(metadata, "url").and_then(|v| to_rust_string(v)),
+            )
+        };
+
+        let thrown = runtime::current_liveness()
+            .and_then(|alive| PersistentValue::from_raw(exception, alive).ok())
+            .map(Arc::new);
+
+        Some(Error::JsException(Box::new(JsException {
+            code,
+            message,
+            name,
+            stack,
+            line,
+            column,
+            source,
+            url,
+            thrown: Thrown(thrown),
+        })))
+    }
+}
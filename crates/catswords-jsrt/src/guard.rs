@@ -1,15 +1,57 @@
 use crate::runtime::Runtime;
 use crate::context::Context;
+use crate::error::{err_msg, Result};
 use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::JsErrorCode;
+use std::cell::Cell;
 
+thread_local! {
+    // The runtime entered by this thread's guards, and how many of them are live.
+    static ENTERED: Cell<(sys::JsRuntimeHandle, usize)> = const { Cell::new((std::ptr::null_mut(), 0)) };
+}
+
+// Keeps a context current. Values borrow the guard they came from, which ties
+// them to its runtime but not to its context: with nested guards of two contexts
+// of one runtime, a value of the outer one can be passed to the inner one. Use
+// `Value::check_context` where that matters.
 pub struct Guard<'rt> {
     pub(crate) prev: sys::JsContextRef,
     pub(crate) current: sys::JsContextRef,
     pub(crate) runtime: &'rt Runtime,
-    pub(crate) _marker: std::marker::PhantomData<&'rt ()>,
+    // Made by `Context::make_current`, and so counted in ENTERED.
+    pub(crate) entered: bool,
+    // Not Send or Sync: the current context is per-thread state.
+    pub(crate) _marker: std::marker::PhantomData<(&'rt (), *mut ())>,
 }
 
 impl<'rt> Guard<'rt> {
+    // For engine callbacks, which run inside a guard already: the context the
+    // engine made current stays current afterwards.
+    pub(crate) fn in_callback(runtime: &'rt Runtime) -> Self {
+        let mut current: sys::JsContextRef = std::ptr::null_mut();
+        unsafe {
+            let _ = sys::JsGetCurrentContext(&mut current);
+        }
+        Self { prev: current, current, runtime, entered: false, _marker: std::marker::PhantomData }
+    }
+
+    // Count a new guard of `runtime` on this thread. Guards of two runtimes never
+    // coexist, so a value borrowed from one guard only ever meets guards of its
+    // own runtime.
+    pub(crate) fn enter(runtime: &Runtime) -> Result<()> {
+        ENTERED.with(|entered| {
+            let (raw, count) = entered.get();
+            if count > 0 && raw != runtime.raw() {
+                return Err(err_msg(
+                    JsErrorCode::JsErrorWrongRuntime,
+                    "a guard of another runtime is live on this thread".to_string(),
+                ));
+            }
+            entered.set((runtime.raw(), count + 1));
+            Ok(())
+        })
+    }
+
     pub fn context_raw(&self) -> sys::JsContextRef {
         self.current
     }
@@ -25,6 +67,12 @@ impl<'rt> Guard<'rt> {
 
 impl Drop for Guard<'_> {
     fn drop(&mut self) {
+        if self.entered {
+            ENTERED.with(|entered| {
+                let (raw, count) = entered.get();
+                entered.set((raw, count - 1));
+            });
+        }
         unsafe {
             let _ = sys::JsSetCurrentContext(self.prev);
         }
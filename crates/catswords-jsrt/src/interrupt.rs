@@ -0,0 +1,161 @@
+use crate::error::{ok_msg, Result};
+use catswords_jsrt_sys as sys;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// The runtime handle as seen from other threads. It is cleared (under the lock)
+// before the runtime is disposed, so a handle never touches a dead runtime.
+pub(crate) struct InterruptTarget {
+    raw: Mutex<Option<RawRuntime>>,
+}
+
+struct RawRuntime(sys::JsRuntimeHandle);
+
+// JsDisableRuntimeExecution is documented as callable from any thread.
+unsafe impl Send for RawRuntime {}
+
+impl InterruptTarget {
+    pub(crate) fn new(raw: sys::JsRuntimeHandle) -> Arc<Self> {
+        Arc::new(Self { raw: Mutex::new(Some(RawRuntime(raw))) })
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.raw.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+// Stops script running on a runtime from any thread. Obtained from
+// `Runtime::interrupt_handle`; the runtime must allow script interrupts.
+#[derive(Clone)]
+pub struct InterruptHandle {
+    target: Arc<InterruptTarget>,
+}
+
+impl InterruptHandle {
+    pub(crate) fn new(target: Arc<InterruptTarget>) -> Self {
+        Self { target }
+    }
+
+    // Terminate whatever script is running (or runs next). The interrupted call
+    // fails with `Error::ScriptTerminated`, and the runtime refuses to run script
+    // until `Runtime::enable_execution` is called. A no-op once the runtime is gone.
+    pub fn interrupt(&self) -> Result<()> {
+        let raw = self.target.raw.lock().unwrap_or_else(|e| e.into_inner());
+        match &*raw {
+            Some(rt) => unsafe {
+                ok_msg(sys::JsDisableRuntimeExecution(rt.0), "JsDisableRuntimeExecution failed")
+            },
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_runtime_alive(&self) -> bool {
+        self.target.raw.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+}
+
+// One thread per runtime that interrupts `script::eval_with_timeout` calls past
+// their deadline. Calls may nest (a native callback running its own timed
+// eval), so every armed call has an entry; the interrupt terminates the whole
+// script stack, so it marks all of them.
+pub(crate) struct Watchdog {
+    shared: Arc<WatchdogShared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+struct WatchdogShared {
+    state: Mutex<WatchdogState>,
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct WatchdogState {
+    armed: Vec<Armed>,
+    next_id: u64,
+    shutdown: bool,
+}
+
+struct Armed {
+    id: u64,
+    deadline: Instant,
+    fired: bool,
+}
+
+impl Watchdog {
+    pub(crate) fn spawn(target: Arc<InterruptTarget>) -> Self {
+        let shared = Arc::new(WatchdogShared { state: Mutex::new(WatchdogState::default()), wake: Condvar::new() });
+        let thread = {
+            let shared = shared.clone();
+            let handle = InterruptHandle::new(target);
+            thread::spawn(move || shared.run(&handle))
+        };
+        Self { shared, thread: Some(thread) }
+    }
+
+    pub(crate) fn arm(&self, timeout: Duration) -> u64 {
+        let mut state = self.shared.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        // A timeout too large for Instant never fires.
+        let deadline = Instant::now().checked_add(timeout).unwrap_or_else(far_future);
+        state.armed.push(Armed { id, deadline, fired: false });
+        self.shared.wake.notify_one();
+        id
+    }
+
+    // True when the watchdog fired for this call and no enclosing call that it
+    // also terminated is still running: then execution is this call's to re-enable.
+    pub(crate) fn disarm(&self, id: u64) -> bool {
+        let mut state = self.shared.lock();
+        let fired = match state.armed.iter().position(|a| a.id == id) {
+            Some(i) => state.armed.remove(i).fired,
+            None => false,
+        };
+        fired && !state.armed.iter().any(|a| a.fired)
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.wake.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl WatchdogShared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, WatchdogState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn run(&self, handle: &InterruptHandle) {
+        let mut state = self.lock();
+        while !state.shutdown {
+            let next = state.armed.iter().filter(|a| !a.fired).map(|a| a.deadline).min();
+            state = match next {
+                None => self.wake.wait(state).unwrap_or_else(|e| e.into_inner()),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now < deadline {
+                        self.wake.wait_timeout(state, deadline - now).unwrap_or_else(|e| e.into_inner()).0
+                    } else {
+                        if handle.interrupt().is_ok() {
+                            state.armed.iter_mut().for_each(|a| a.fired = true);
+                        } else {
+                            // Nothing to interrupt; do not retry this deadline.
+                            state.armed.retain(|a| a.deadline > now);
+                        }
+                        state
+                    }
+                }
+            };
+        }
+    }
+}
+
+fn far_future() -> Instant {
+    Instant::now() + Duration::from_secs(60 * 60 * 24 * 365 * 30)
+}
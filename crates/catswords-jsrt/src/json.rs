@@ -0,0 +1,81 @@
+use crate::error::{type_err, Result};
+use crate::guard::Guard;
+use crate::value::{FromJs, Function, Number, Object, Value};
+
+// JSON through the engine's own `JSON` object, so the text is never spliced
+// into script source. Invalid JSON (a SyntaxError) and cyclic values (a
+// TypeError) come back as `Error::JsException` with the engine's message;
+// errors from a reviver or replacer are propagated the same way.
+
+fn json_function<'g>(guard: &'g Guard<'_>, name: &str) -> Result<Function<'g>> {
+    let json = Object::global(guard)?.get(guard, "JSON")?;
+    let json = json.as_object(guard).ok_or_else(|| type_err("global JSON is missing".to_string()))?;
+    json.get(guard, name)?
+        .as_function(guard)
+        .ok_or_else(|| type_err(format!("JSON.{} is not a function", name)))
+}
+
+// Wraps a `(key, value)` Rust callback as the reviver / replacer function.
+fn key_value_callback<'g, F>(guard: &'g Guard<'_>, f: F) -> Function<'g>
+where
+    F: for<'a> Fn(&'a Guard<'_>, &str, Value<'a>) -> Result<Value<'a>> + Send + 'static,
+{
+    Function::new(guard, Box::new(move |guard, info| {
+        let key = match info.arguments.first() {
+            Some(key) => String::from_js(guard, key)?,
+            None => String::new(),
+        };
+        let value = match info.arguments.get(1) {
+            Some(v) => *v,
+            None => Value::undefined(guard)?,
+        };
+        f(guard, &key, value)
+    }))
+}
+
+pub fn parse<'g>(guard: &'g Guard<'_>, text: &str) -> Result<Value<'g>> {
+    let parse = json_function(guard, "parse")?;
+    parse.call_plain(guard, &[&Value::string_utf8(guard, text)?])
+}
+
+// `JSON.parse(text, reviver)`: `reviver` sees every key and parsed value, innermost
+// first, and returns the value to keep (undefined removes the property).
+pub fn parse_with_reviver<'g, F>(guard: &'g Guard<'_>, text: &str, reviver: F) -> Result<Value<'g>>
+where
+    F: for<'a> Fn(&'a Guard<'_>, &str, Value<'a>) -> Result<Value<'a>> + Send + 'static,
+{
+    let parse = json_function(guard, "parse")?;
+    let reviver = key_value_callback(guard, reviver);
+    parse.call_plain(guard, &[&Value::string_utf8(guard, text)?, reviver.as_value()])
+}
+
+// `JSON.stringify(value, null, indent)`; an indent of 0 gives compact output and
+// the engine caps it at 10. Values JSON cannot represent at all (undefined, a
+// function or a symbol) fail with `Error::Type`.
+pub fn stringify(guard: &Guard<'_>, value: &Value<'_>, indent: usize) -> Result<String> {
+    stringify_inner(guard, value, indent, None)
+}
+
+// Like `stringify`, with `JSON.stringify`'s replacer: called for every key and
+// value (the root has key ""), returning the value to write instead.
+pub fn stringify_with_replacer<F>(guard: &Guard<'_>, value: &Value<'_>, indent: usize, replacer: F) -> Result<String>
+where
+    F: for<'a> Fn(&'a Guard<'_>, &str, Value<'a>) -> Result<Value<'a>> + Send + 'static,
+{
+    let replacer = key_value_callback(guard, replacer);
+    stringify_inner(guard, value, indent, Some(replacer))
+}
+
+fn stringify_inner(guard: &Guard<'_>, value: &Value<'_>, indent: usize, replacer: Option<Function<'_>>) -> Result<String> {
+    let stringify = json_function(guard, "stringify")?;
+    let replacer = match replacer {
+        Some(f) => f.into(),
+        None => Value::null(guard)?,
+    };
+    let indent: Value<'_> = Number::new(guard, indent.min(10) as i32).into();
+    let out = stringify.call_plain(guard, &[value, &replacer, &indent])?;
+    if out.is_undefined(guard) {
+        return Err(type_err(format!("{} cannot be converted to JSON", value.type_name(guard))));
+    }
+    String::from_js(guard, &out)
+}
@@ -1,16 +1,29 @@
 mod error;
+mod exception;
 mod runtime;
 mod context;
 mod guard;
 mod root;
+mod interrupt;
+mod memory;
 
 pub mod script;
+pub mod json;
+pub mod module;
 pub mod value;
+#[cfg(feature = "serde")]
+pub mod serde;
 
 pub use error::{Error, Result};
-pub use runtime::Runtime;
+pub use exception::JsException;
+pub use runtime::{PanicPolicy, Runtime, RuntimeBuilder, SendableRuntime};
 pub use context::Context;
 pub use guard::Guard;
 pub use root::{RootStore, RootedValue};
-pub use catswords_jsrt_sys::JsErrorCode;
-pub use error::err_msg;
+pub use interrupt::InterruptHandle;
+pub use memory::MemoryEvent;
+pub use catswords_jsrt_sys::{JsErrorCategory, JsErrorCode, JsRuntimeAttributes, JsTypedArrayType, JsValueType};
+pub use error::{err_msg, type_err};
+
+#[cfg(feature = "macros")]
+pub use catswords_jsrt_macros::{js_class, js_function, js_methods};
@@ -0,0 +1,177 @@
+use crate::error::{ok_msg, Result};
+use crate::guard::Guard;
+use crate::runtime::{Runtime, RuntimeInner};
+use crate::value::report_panic;
+use catswords_jsrt_sys as sys;
+use std::ffi::c_void;
+use std::rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryEvent {
+    Allocate,
+    Free,
+    // An allocation failed, either in the OS or because it was rejected.
+    Failure,
+}
+
+// Returning false from an `Allocate` event rejects the allocation; the return
+// value is ignored for the other events. Runs on whatever thread allocates,
+// which may be one of the engine's background threads.
+pub(crate) type MemoryCallback = dyn Fn(MemoryEvent, usize) -> bool + Send + Sync + 'static;
+
+// Runs on the runtime's thread right before each collection.
+pub(crate) type BeforeCollectCallback = dyn Fn() + Send + 'static;
+
+// The runtime comes along so a panic can go through its panic policy.
+pub(crate) struct BeforeCollectState {
+    runtime: rc::Weak<RuntimeInner>,
+    cb: Box<BeforeCollectCallback>,
+}
+
+// ChakraCore's "no limit" value.
+const NO_LIMIT: usize = usize::MAX;
+
+impl Runtime {
+    // Cap the runtime's heap. Allocations past the limit fail with
+    // `Error::OutOfMemory` (or a script-level out-of-memory Error); build the
+    // runtime with `disable_fatal_on_oom(true)` so this never aborts the process.
+    pub fn set_memory_limit(&self, limit: Option<usize>) -> Result<()> {
+        unsafe {
+            ok_msg(
+                sys::JsSetRuntimeMemoryLimit(self.raw(), limit.unwrap_or(NO_LIMIT)),
+                "JsSetRuntimeMemoryLimit failed",
+            )
+        }
+    }
+
+    pub fn memory_limit(&self) -> Result<Option<usize>> {
+        let mut limit: usize = 0;
+        unsafe {
+            ok_msg(sys::JsGetRuntimeMemoryLimit(self.raw(), &mut limit), "JsGetRuntimeMemoryLimit failed")?;
+        }
+        Ok(if limit == NO_LIMIT { None } else { Some(limit) })
+    }
+
+    // Bytes currently allocated by the runtime.
+    pub fn memory_usage(&self) -> Result<usize> {
+        let mut usage: usize = 0;
+        unsafe {
+            ok_msg(sys::JsGetRuntimeMemoryUsage(self.raw(), &mut usage), "JsGetRuntimeMemoryUsage failed")?;
+        }
+        Ok(usage)
+    }
+
+    pub fn set_memory_allocation_callback<F>(&self, callback: Option<F>) -> Result<()>
+    where
+        F: Fn(MemoryEvent, usize) -> bool + Send + Sync + 'static,
+    {
+        let mut slot = self.inner.memory_callback.borrow_mut();
+        match callback {
+            Some(f) => {
+                // Double box so the engine gets a thin pointer.
+                let boxed: Box<Box<MemoryCallback>> = Box::new(Box::new(f));
+                let state = &*boxed as *const Box<MemoryCallback> as *mut c_void;
+                unsafe {
+                    ok_msg(
+                        sys::JsSetRuntimeMemoryAllocationCallback(self.raw(), state, Some(memory_trampoline)),
+                        "JsSetRuntimeMemoryAllocationCallback failed",
+                    )?;
+                }
+                // The engine now points at the new box, but an allocation on another
+                // thread may still be inside the old one; keep it until disposal.
+                self.retire(slot.replace(boxed));
+            }
+            None => {
+                unsafe {
+                    ok_msg(
+                        sys::JsSetRuntimeMemoryAllocationCallback(self.raw(), std::ptr::null_mut(), None),
+                        "JsSetRuntimeMemoryAllocationCallback failed",
+                    )?;
+                }
+                self.retire(slot.take());
+            }
+        }
+        Ok(())
+    }
+
+    // Run a full, blocking collection now.
+    pub fn collect_garbage(&self) -> Result<()> {
+        unsafe { ok_msg(sys::JsCollectGarbage(self.raw()), "JsCollectGarbage failed")?; }
+        self.drop_deferred();
+        Ok(())
+    }
+
+    // Let the engine do deferred work (GC, cleanup) while the host is quiet.
+    // Needs a current context and `enable_idle_processing(true)`. Returns the tick
+    // count (in the GetTickCount sense, milliseconds) at which to call it again.
+    pub fn idle(&self, _guard: &Guard<'_>) -> Result<u32> {
+        let mut next_idle_tick: u32 = 0;
+        unsafe { ok_msg(sys::JsIdle(&mut next_idle_tick), "JsIdle failed")?; }
+        Ok(next_idle_tick)
+    }
+
+    pub fn set_before_collect_callback<F>(&self, callback: Option<F>) -> Result<()>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let mut slot = self.inner.before_collect_callback.borrow_mut();
+        match callback {
+            Some(f) => {
+                let boxed = Box::new(BeforeCollectState { runtime: self.handle(), cb: Box::new(f) });
+                let state = &*boxed as *const BeforeCollectState as *mut c_void;
+                unsafe {
+                    ok_msg(
+                        sys::JsSetRuntimeBeforeCollectCallback(self.raw(), state, Some(before_collect_trampoline)),
+                        "JsSetRuntimeBeforeCollectCallback failed",
+                    )?;
+                }
+                self.retire(slot.replace(boxed));
+            }
+            None => {
+                unsafe {
+                    ok_msg(
+                        sys::JsSetRuntimeBeforeCollectCallback(self.raw(), std::ptr::null_mut(), None),
+                        "JsSetRuntimeBeforeCollectCallback failed",
+                    )?;
+                }
+                self.retire(slot.take());
+            }
+        }
+        Ok(())
+    }
+
+    fn retire<T: 'static>(&self, old: Option<Box<T>>) {
+        if let Some(old) = old {
+            self.inner.retired_callbacks.borrow_mut().push(old);
+        }
+    }
+}
+
+unsafe extern "C" fn before_collect_trampoline(callback_state: *mut c_void) {
+    let st = &*(callback_state as *const BeforeCollectState);
+    if let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(&st.cb)) {
+        // There is no script to throw into, so Throw drops the panic; the other
+        // policies log it or abort as usual. Nothing is left to report to once
+        // disposal has begun.
+        if let Some(runtime) = Runtime::from_handle(&st.runtime) {
+            report_panic(&runtime, "before-collect callback", &*payload);
+        }
+    }
+}
+
+unsafe extern "C" fn memory_trampoline(
+    callback_state: *mut c_void,
+    event: sys::JsMemoryEventType,
+    size: usize,
+) -> bool {
+    let cb = &*(callback_state as *const Box<MemoryCallback>);
+    let event = match event {
+        sys::JsMemoryEventType::JsMemoryAllocate => MemoryEvent::Allocate,
+        sys::JsMemoryEventType::JsMemoryFree => MemoryEvent::Free,
+        sys::JsMemoryEventType::JsMemoryFailure => MemoryEvent::Failure,
+        // An event this crate does not know; nothing to report, never veto it.
+        _ => return true,
+    };
+    // A panicking observer must not unwind into the allocator; treat it as a veto.
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cb(event, size))).unwrap_or(false)
+}
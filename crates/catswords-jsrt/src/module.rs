@@ -0,0 +1,520 @@
+use crate::context::Context;
+use crate::error::{err_msg, ok, ok_msg, type_err, Error, Result};
+use crate::exception;
+use crate::guard::Guard;
+use crate::runtime::{Runtime, RuntimeInner};
+use crate::value::{report_panic, Object, Value};
+use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::{JsErrorCode, JsModuleHostInfoKind};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::c_void;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Component, Path, PathBuf};
+use std::rc;
+
+// Decides where the ES modules of a context come from. The names returned by
+// `resolve` identify modules: each one is loaded and evaluated once per context.
+// Runs on the runtime's thread, but must be Send so the runtime can move as a
+// SendableRuntime.
+pub trait ModuleLoader: Send + 'static {
+    // Turn an import specifier into a module name. `referrer` is the name of the
+    // importing module, or None for `module::import` and `import()` in classic
+    // scripts. By default the specifier is the name.
+    fn resolve(&self, specifier: &str, _referrer: Option<&str>) -> Result<String> {
+        Ok(specifier.to_string())
+    }
+
+    // Source text of the module `name`.
+    fn load(&self, name: &str) -> Result<String>;
+}
+
+fn not_found(name: &str) -> Error {
+    err_msg(JsErrorCode::JsErrorInvalidArgument, format!("module {:?} not found", name))
+}
+
+// Modules held in memory (e.g. embedded with `include_str!`), keyed by specifier.
+impl ModuleLoader for HashMap<String, String> {
+    fn load(&self, name: &str) -> Result<String> {
+        self.get(name).cloned().ok_or_else(|| not_found(name))
+    }
+}
+
+// Modules read from the file system. Specifiers starting with "./" or "../" are
+// relative to the importing module, all others to `root`; module names are the
+// resulting paths. This is not a sandbox: "../" may leave `root`.
+#[derive(Debug, Clone)]
+pub struct FileLoader {
+    root: PathBuf,
+}
+
+impl FileLoader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl ModuleLoader for FileLoader {
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String> {
+        let relative = specifier.starts_with("./") || specifier.starts_with("../");
+        let base = match referrer {
+            Some(referrer) if relative => Path::new(referrer).parent().map(Path::to_path_buf).unwrap_or_default(),
+            _ => self.root.clone(),
+        };
+        let path = normalize(&base.join(specifier.trim_start_matches('/')));
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    fn load(&self, name: &str) -> Result<String> {
+        fs::read_to_string(name).map_err(|e| {
+            err_msg(JsErrorCode::JsErrorInvalidArgument, format!("cannot read module {}: {}", name, e))
+        })
+    }
+}
+
+// Drop "." and fold ".." lexically, so one file always gets the same name.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+impl Context<'_> {
+    // Load the modules of this context through `loader` from now on. Modules
+    // loaded before stay cached under their names.
+    pub fn set_module_loader<L: ModuleLoader>(&self, loader: L) -> Result<()> {
+        let guard = self.make_current()?;
+        let host = ModuleHost::of(&guard)?;
+        *host.loader.borrow_mut() = Some(Box::new(loader));
+        Ok(())
+    }
+}
+
+// Load `specifier` and everything it imports through the context's loader,
+// evaluate it and return its namespace object. A module that was imported
+// before is not loaded again.
+pub fn import<'g>(guard: &'g Guard<'_>, specifier: &str) -> Result<Object<'g>> {
+    let host = ModuleHost::of(guard)?;
+    host.failure.take();
+    let name = host.resolve(specifier, None)?;
+    let cached = host.records.borrow().get(&name).copied();
+    let record = match cached {
+        Some(record) => record,
+        None => {
+            let source = host.load(&name)?;
+            host.parse_root(guard, &name, &source)?
+        }
+    };
+    host.finish(guard, record)
+}
+
+// Evaluate `source` as the module `name` and return its namespace object. Only
+// its imports go through the loader; other modules can then import it by name.
+pub fn eval_module<'g>(guard: &'g Guard<'_>, name: &str, source: &str) -> Result<Object<'g>> {
+    let host = ModuleHost::of(guard)?;
+    host.failure.take();
+    if host.records.borrow().contains_key(name) {
+        return Err(err_msg(JsErrorCode::JsErrorModuleParsed, format!("module {:?} is already loaded", name)));
+    }
+    let record = host.parse_root(guard, name, source)?;
+    host.finish(guard, record)
+}
+
+// Load and evaluate the modules requested with `import()` since the last module
+// call, e.g. by a classic script run through `script::eval`. Failures reject
+// the `import()` promise instead of being returned here.
+pub fn load_pending(guard: &Guard<'_>) -> Result<()> {
+    let host = ModuleHost::of(guard)?;
+    host.drive(guard, std::ptr::null_mut())?;
+    host.failure.take();
+    Ok(())
+}
+
+// Per-context module state, reached from the engine's callbacks through the
+// context data. Owned by the runtime and dropped after JsDisposeRuntime.
+pub(crate) struct ModuleHost {
+    // Heap-stable, so still valid after the runtime has moved.
+    runtime: rc::Weak<RuntimeInner>,
+    loader: RefCell<Option<Box<dyn ModuleLoader>>>,
+    records: RefCell<HashMap<String, sys::JsModuleRecord>>,
+    names: RefCell<HashMap<sys::JsModuleRecord, String>>,
+    // Imported modules waiting for their source; Err if the specifier did not resolve.
+    pending: RefCell<VecDeque<(sys::JsModuleRecord, Result<String>)>>,
+    // Module trees the engine reported ready, with the exception (AddRef'd) if one failed.
+    ready: RefCell<Vec<(sys::JsModuleRecord, sys::JsValueRef)>>,
+    // The first loader error since the last entry; more telling than the Error
+    // the importing module fails with.
+    failure: RefCell<Option<Error>>,
+    next_source_context: Cell<sys::JsSourceContext>,
+}
+
+impl ModuleHost {
+    // The host of the current context, installed along with the callbacks on first use.
+    fn of<'a>(guard: &'a Guard<'_>) -> Result<&'a ModuleHost> {
+        let mut data: *mut c_void = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsGetContextData(guard.current, &mut data), "JsGetContextData failed")?; }
+        if !data.is_null() {
+            return Ok(unsafe { &*(data as *const ModuleHost) });
+        }
+
+        let host = Box::new(ModuleHost {
+            runtime: guard.runtime().handle(),
+            loader: RefCell::new(None),
+            records: RefCell::new(HashMap::new()),
+            names: RefCell::new(HashMap::new()),
+            pending: RefCell::new(VecDeque::new()),
+            ready: RefCell::new(Vec::new()),
+            failure: RefCell::new(None),
+            next_source_context: Cell::new(1),
+        });
+        let ptr = Box::into_raw(host) as *mut c_void;
+        // Owned by the runtime from here on, even if installing the callbacks fails.
+        guard.runtime().inner.module_hosts.borrow_mut().push(ptr as *mut ModuleHost);
+        unsafe {
+            // The callbacks belong to the context; any module record can set them.
+            let mut record: sys::JsModuleRecord = std::ptr::null_mut();
+            ok_msg(
+                sys::JsInitializeModuleRecord(std::ptr::null_mut(), std::ptr::null_mut(), &mut record),
+                "JsInitializeModuleRecord failed",
+            )?;
+            set_host_info(
+                record,
+                JsModuleHostInfoKind::JsModuleHostInfo_FetchImportedModuleCallback,
+                fetch_imported_module as *const () as *mut c_void,
+            )?;
+            set_host_info(
+                record,
+                JsModuleHostInfoKind::JsModuleHostInfo_FetchImportedModuleFromScriptCallback,
+                fetch_imported_module_from_script as *const () as *mut c_void,
+            )?;
+            set_host_info(
+                record,
+                JsModuleHostInfoKind::JsModuleHostInfo_NotifyModuleReadyCallback,
+                notify_module_ready as *const () as *mut c_void,
+            )?;
+            ok_msg(sys::JsSetContextData(guard.current, ptr), "JsSetContextData failed")?;
+        }
+        Ok(unsafe { &*(ptr as *const ModuleHost) })
+    }
+
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String> {
+        match &*self.loader.borrow() {
+            Some(loader) => loader.resolve(specifier, referrer),
+            None => Err(err_msg(
+                JsErrorCode::JsErrorInvalidArgument,
+                format!("cannot import {:?}: no module loader is set", specifier),
+            )),
+        }
+    }
+
+    fn load(&self, name: &str) -> Result<String> {
+        match &*self.loader.borrow() {
+            Some(loader) => loader.load(name),
+            None => Err(not_found(name)),
+        }
+    }
+
+    fn fail(&self, error: Error) {
+        let mut failure = self.failure.borrow_mut();
+        if failure.is_none() {
+            *failure = Some(error);
+        }
+    }
+
+    // A new record for `name`, cached under it unless `cache` is false.
+    fn create_record(
+        &self,
+        guard: &Guard<'_>,
+        referencing: sys::JsModuleRecord,
+        name: &str,
+        cache: bool,
+    ) -> Result<sys::JsModuleRecord> {
+        let specifier = Value::string_utf8(guard, name)?;
+        let mut record: sys::JsModuleRecord = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsInitializeModuleRecord(referencing, specifier.raw(), &mut record),
+                "JsInitializeModuleRecord failed",
+            )?;
+            // Shows up as the url of exceptions thrown by the module.
+            set_host_info(record, JsModuleHostInfoKind::JsModuleHostInfo_Url, specifier.raw())?;
+        }
+        self.names.borrow_mut().insert(record, name.to_string());
+        if cache {
+            self.records.borrow_mut().insert(name.to_string(), record);
+        }
+        Ok(record)
+    }
+
+    // Imports met while parsing are fetched and queued; nothing is borrowed across
+    // the call, since those callbacks run inside it.
+    fn parse(&self, record: sys::JsModuleRecord, source: &str) -> Result<()> {
+        let len = u32::try_from(source.len())
+            .map_err(|_| type_err(format!("module source of {} bytes is too large", source.len())))?;
+        let source_context = self.next_source_context.get();
+        self.next_source_context.set(source_context + 1);
+
+        let mut exception: sys::JsValueRef = std::ptr::null_mut();
+        let code = unsafe {
+            sys::JsParseModuleSource(
+                record,
+                source_context,
+                source.as_ptr() as *mut u8,
+                len,
+                sys::JsParseModuleSourceFlags::JsParseModuleSourceFlags_DataIsUTF8,
+                &mut exception,
+            )
+        };
+        if code == JsErrorCode::JsNoError || exception.is_null() {
+            return ok_msg(code, "JsParseModuleSource failed");
+        }
+        Err(unsafe { thrown(exception, code) })
+    }
+
+    // A root module fails right away on a syntax error, and is then forgotten so
+    // that a later import can try again.
+    fn parse_root(&self, guard: &Guard<'_>, name: &str, source: &str) -> Result<sys::JsModuleRecord> {
+        let record = self.create_record(guard, std::ptr::null_mut(), name, true)?;
+        if let Err(e) = self.parse(record, source) {
+            self.records.borrow_mut().remove(name);
+            return Err(e);
+        }
+        Ok(record)
+    }
+
+    // Called from the engine while it parses an importing module or runs `import()`.
+    fn fetch(&self, guard: &Guard<'_>, referencing: sys::JsModuleRecord, specifier: &Value<'_>) -> Result<sys::JsModuleRecord> {
+        let specifier = specifier.to_rust_string(guard)?;
+        let referrer = self.names.borrow().get(&referencing).cloned();
+        let resolved = match panic::catch_unwind(AssertUnwindSafe(|| self.resolve(&specifier, referrer.as_deref()))) {
+            Ok(resolved) => resolved,
+            Err(payload) => Err(loader_panicked(guard, &*payload)?),
+        };
+
+        let name = match &resolved {
+            Ok(name) => {
+                if let Some(&record) = self.records.borrow().get(name) {
+                    return Ok(record);
+                }
+                name.as_str()
+            }
+            // Still hand out a record; it fails once the queue reaches it, which
+            // reports the error to the importing module.
+            Err(_) => specifier.as_str(),
+        };
+        let record = self.create_record(guard, referencing, name, resolved.is_ok())?;
+        self.pending.borrow_mut().push_back((record, resolved));
+        Ok(record)
+    }
+
+    // Load and parse a queued module. Syntax errors the engine reports to the
+    // importing modules itself; every other failure (including a panicking
+    // loader) is set on the module as its exception, or an `import()` of it
+    // would never settle. Either way the module is forgotten, so that a later
+    // import can try again.
+    fn load_dependency(&self, guard: &Guard<'_>, record: sys::JsModuleRecord, name: Result<String>) -> Result<()> {
+        let source = name.and_then(|name| match panic::catch_unwind(AssertUnwindSafe(|| self.load(&name))) {
+            Ok(source) => source,
+            Err(payload) => Err(loader_panicked(guard, &*payload)?),
+        });
+        let error = match source {
+            Ok(source) => match self.parse(record, &source) {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    self.forget(record);
+                    self.fail(e);
+                    return Ok(());
+                }
+            },
+            Err(e) => e,
+        };
+        self.forget(record);
+        let exception = Value::error_from_message(guard, &error.to_string())?;
+        unsafe {
+            set_host_info(record, JsModuleHostInfoKind::JsModuleHostInfo_Exception, exception.raw())?;
+        }
+        self.fail(error);
+        Ok(())
+    }
+
+    fn forget(&self, record: sys::JsModuleRecord) {
+        if let Some(name) = self.names.borrow_mut().remove(&record) {
+            let mut records = self.records.borrow_mut();
+            if records.get(&name) == Some(&record) {
+                records.remove(&name);
+            }
+        }
+    }
+
+    // Work through the queued imports until none are left. Module trees loaded
+    // by `import()` are evaluated, which settles their promises; the ready
+    // notification of `root` is left for the caller.
+    fn drive(&self, guard: &Guard<'_>, root: sys::JsModuleRecord) -> Result<()> {
+        loop {
+            let next = self.pending.borrow_mut().pop_front();
+            if let Some((record, name)) = next {
+                self.load_dependency(guard, record, name)?;
+                continue;
+            }
+
+            let dynamic: Vec<_> = {
+                let mut ready = self.ready.borrow_mut();
+                let (own, dynamic) = ready.drain(..).partition(|(record, _)| *record == root);
+                *ready = own;
+                dynamic
+            };
+            if dynamic.is_empty() {
+                return Ok(());
+            }
+            for (record, exception) in dynamic {
+                unsafe {
+                    release(exception);
+                    let mut result: sys::JsValueRef = std::ptr::null_mut();
+                    if sys::JsModuleEvaluation(record, &mut result) != JsErrorCode::JsNoError {
+                        exception::discard_pending();
+                    }
+                }
+            }
+        }
+    }
+
+    fn take_ready(&self, record: sys::JsModuleRecord) -> Option<sys::JsValueRef> {
+        let mut ready = self.ready.borrow_mut();
+        let index = ready.iter().position(|(r, _)| *r == record)?;
+        Some(ready.remove(index).1)
+    }
+
+    // Load the imports of a parsed root module, then evaluate it.
+    fn finish<'g>(&self, guard: &'g Guard<'_>, record: sys::JsModuleRecord) -> Result<Object<'g>> {
+        self.drive(guard, record)?;
+        let failure = self.failure.take();
+        match self.take_ready(record) {
+            Some(exception) if !exception.is_null() => {
+                let error = unsafe { thrown(exception, JsErrorCode::JsErrorScriptException) };
+                unsafe { release(exception) };
+                return Err(failure.unwrap_or(error));
+            }
+            // A cached module is never reported ready again.
+            None => {
+                if let Some(failure) = failure {
+                    return Err(failure);
+                }
+            }
+            Some(_) => {}
+        }
+
+        let mut result: sys::JsValueRef = std::ptr::null_mut();
+        let mut namespace: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok(sys::JsModuleEvaluation(record, &mut result))?;
+            ok_msg(sys::JsGetModuleNamespace(record, &mut namespace), "JsGetModuleNamespace failed")?;
+        }
+        // Evaluation may have started `import()` calls.
+        self.drive(guard, std::ptr::null_mut())?;
+        self.failure.take();
+        Ok(Object::from_value(Value::from_raw(namespace)))
+    }
+}
+
+unsafe fn set_host_info(record: sys::JsModuleRecord, kind: JsModuleHostInfoKind, info: *mut c_void) -> Result<()> {
+    ok_msg(sys::JsSetModuleHostInfo(record, kind, info), "JsSetModuleHostInfo failed")
+}
+
+unsafe fn release(value: sys::JsValueRef) {
+    if !value.is_null() {
+        let _ = sys::JsRelease(value, std::ptr::null_mut());
+    }
+}
+
+// Describe `exception` as `Error::JsException` by passing it through the
+// engine's exception state, like any value thrown by script.
+// The error a panic in the loader turns into, after the runtime's panic policy.
+fn loader_panicked(guard: &Guard<'_>, payload: &(dyn Any + Send)) -> Result<Error> {
+    let msg = report_panic(guard.runtime(), "module loader", payload);
+    let error = Value::error_from_message(guard, &msg)?;
+    Ok(unsafe { thrown(error.raw(), JsErrorCode::JsErrorScriptException) })
+}
+
+unsafe fn thrown(exception: sys::JsValueRef, code: JsErrorCode) -> Error {
+    exception::discard_pending();
+    let _ = sys::JsSetException(exception);
+    exception::take_pending(code).unwrap_or_else(|| err_msg(code, "module failed to load".to_string()))
+}
+
+unsafe fn current_host<'a>() -> Option<&'a ModuleHost> {
+    let mut current: sys::JsContextRef = std::ptr::null_mut();
+    let mut data: *mut c_void = std::ptr::null_mut();
+    if sys::JsGetCurrentContext(&mut current) != JsErrorCode::JsNoError
+        || current.is_null()
+        || sys::JsGetContextData(current, &mut data) != JsErrorCode::JsNoError
+        || data.is_null()
+    {
+        return None;
+    }
+    Some(&*(data as *const ModuleHost))
+}
+
+unsafe fn fetch(
+    referencing: sys::JsModuleRecord,
+    specifier: sys::JsValueRef,
+    dependent_module_record: *mut sys::JsModuleRecord,
+) -> JsErrorCode {
+    let Some(host) = current_host() else {
+        return JsErrorCode::JsErrorNoCurrentContext;
+    };
+    let Some(runtime) = Runtime::from_handle(&host.runtime) else {
+        return JsErrorCode::JsErrorNoCurrentContext;
+    };
+    let guard = Guard::in_callback(&runtime);
+    match host.fetch(&guard, referencing, &Value::from_raw(specifier)) {
+        Ok(record) => {
+            *dependent_module_record = record;
+            JsErrorCode::JsNoError
+        }
+        Err(e) => {
+            let code = e.code();
+            host.fail(e);
+            code
+        }
+    }
+}
+
+unsafe extern "C" fn fetch_imported_module(
+    referencing_module: sys::JsModuleRecord,
+    specifier: sys::JsValueRef,
+    dependent_module_record: *mut sys::JsModuleRecord,
+) -> JsErrorCode {
+    fetch(referencing_module, specifier, dependent_module_record)
+}
+
+// `import()` in a classic script: there is no importing module to resolve against.
+unsafe extern "C" fn fetch_imported_module_from_script(
+    _referencing_source_context: sys::JsSourceContext,
+    specifier: sys::JsValueRef,
+    dependent_module_record: *mut sys::JsModuleRecord,
+) -> JsErrorCode {
+    fetch(std::ptr::null_mut(), specifier, dependent_module_record)
+}
+
+// Called once a module tree is parsed, or has failed to load.
+unsafe extern "C" fn notify_module_ready(referencing_module: sys::JsModuleRecord, exception_var: sys::JsValueRef) -> JsErrorCode {
+    let Some(host) = current_host() else {
+        return JsErrorCode::JsErrorNoCurrentContext;
+    };
+    // Keep the exception alive until the module is picked up.
+    if !exception_var.is_null() {
+        let _ = sys::JsAddRef(exception_var, std::ptr::null_mut());
+    }
+    host.ready.borrow_mut().push((referencing_module, exception_var));
+    JsErrorCode::JsNoError
+}
@@ -1,41 +1,401 @@
-use crate::error::{ok, Result};
+use crate::error::{err_msg, ok, ok_msg, Result};
+use crate::exception;
+use crate::interrupt::{InterruptHandle, InterruptTarget, Watchdog};
+use crate::memory::{BeforeCollectState, MemoryCallback};
+use crate::module::ModuleHost;
 use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::JsErrorCode;
+use crate::value::apply_panic_policy;
+use std::any::Any;
 use std::ffi::c_void;
-use std::sync::{Arc, Mutex};
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::{self, Rc};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, ThreadId};
 
+// What happens when a native callback panics. The panic never unwinds into
+// ChakraCore; with Throw and LogAndThrow it becomes a JS Error carrying the
+// panic message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanicPolicy {
+    #[default]
+    Throw,
+    Abort,
+    LogAndThrow,
+}
+
+// State other objects may consult after the runtime has moved or died.
+pub(crate) struct RuntimeShared {
+    // None once the runtime is disposed, so nothing calls into it any more.
+    owner: Mutex<Option<ThreadId>>,
+}
+
+impl RuntimeShared {
+    pub(crate) fn on_owner_thread(&self) -> bool {
+        *self.owner.lock().unwrap_or_else(|e| e.into_inner()) == Some(thread::current().id())
+    }
+}
+
+// Live runtimes by engine handle, for code that only knows the current context
+// (exception reporting). Entries are removed before JsDisposeRuntime, so a
+// handle reused by a later runtime never finds a stale entry.
+static RUNTIMES: Mutex<Vec<(usize, Weak<RuntimeShared>)>> = Mutex::new(Vec::new());
+
+// The liveness token of the runtime whose context is current on this thread.
+pub(crate) fn current_liveness() -> Option<Weak<RuntimeShared>> {
+    let mut context: sys::JsContextRef = std::ptr::null_mut();
+    let mut raw: sys::JsRuntimeHandle = std::ptr::null_mut();
+    unsafe {
+        if sys::JsGetCurrentContext(&mut context) != JsErrorCode::JsNoError || context.is_null() {
+            return None;
+        }
+        if sys::JsGetRuntime(context, &mut raw) != JsErrorCode::JsNoError {
+            return None;
+        }
+    }
+    let runtimes = RUNTIMES.lock().unwrap_or_else(|e| e.into_inner());
+    runtimes.iter().find(|(r, _)| *r == raw as usize).map(|(_, alive)| alive.clone())
+}
+
+// A ChakraCore runtime is single-threaded: it is neither Send nor Sync. Use
+// `into_sendable` to hand it to another thread.
 pub struct Runtime {
+    // Heap-stable, so engine callbacks can find the runtime after it has moved.
+    // Callbacks hold weak references only; this is the one strong reference
+    // outside of a running callback.
+    pub(crate) inner: Rc<RuntimeInner>,
+}
+
+pub(crate) struct RuntimeInner {
     pub(crate) raw: sys::JsRuntimeHandle,
 
-    // Keep callback_state pointers alive until runtime disposal
-    callback_states: Arc<Mutex<Vec<*mut c_void>>>,
+    // Callback states of live functions; each is freed when its function is
+    // collected, and whatever is left after JsDisposeRuntime.
+    callback_states: RefCell<HashSet<*mut c_void>>,
+    // Objects created by a `Class`; only these have external data that is an
+    // instance. Removed by the instance finalizer.
+    instances: RefCell<HashSet<usize>>,
+
+    panic_policy: Cell<PanicPolicy>,
+
+    attributes: sys::JsRuntimeAttributes,
+    interrupt: Arc<InterruptTarget>,
+    // Started by the first `eval_with_timeout`; stopped after JsDisposeRuntime.
+    watchdog: OnceCell<Watchdog>,
+
+    // Dropped with the runtime; weak references to it tell whether it is still alive.
+    alive: Arc<RuntimeShared>,
+    check_thread: bool,
+
+    // Dropped with the runtime, after JsDisposeRuntime.
+    pub(crate) memory_callback: RefCell<Option<Box<Box<MemoryCallback>>>>,
+    pub(crate) before_collect_callback: RefCell<Option<Box<BeforeCollectState>>>,
+    // Replaced callbacks, which a background thread may still be running.
+    pub(crate) retired_callbacks: RefCell<Vec<Box<dyn Any>>>,
+    // State released inside a collection, where its drop must not run: JsRelease
+    // is refused there and a panic would unwind into the engine. Dropped on the
+    // next `make_current` or `collect_garbage`, or after JsDisposeRuntime.
+    deferred_drops: RefCell<Vec<Box<dyn Any>>>,
+    // Module state of every context that has used modules (Box::into_raw), freed
+    // along with the callback states.
+    pub(crate) module_hosts: RefCell<Vec<*mut ModuleHost>>,
 }
 
 impl Runtime {
     pub fn new() -> Result<Self> {
+        RuntimeBuilder::new().build()
+    }
+
+    pub fn builder() -> RuntimeBuilder {
+        RuntimeBuilder::new()
+    }
+
+    pub fn panic_policy(&self) -> PanicPolicy {
+        self.inner.panic_policy.get()
+    }
+
+    pub fn set_panic_policy(&self, policy: PanicPolicy) {
+        self.inner.panic_policy.set(policy);
+    }
+
+    pub fn attributes(&self) -> sys::JsRuntimeAttributes {
+        self.inner.attributes
+    }
+
+    // Requires `RuntimeBuilder::allow_script_interrupt(true)`.
+    pub fn interrupt_handle(&self) -> Result<InterruptHandle> {
+        if !self.inner.attributes.contains(sys::JsRuntimeAttributes::JsRuntimeAttributeAllowScriptInterrupt) {
+            return Err(err_msg(
+                JsErrorCode::JsErrorCannotDisableExecution,
+                "runtime was created without allow_script_interrupt".to_string(),
+            ));
+        }
+        Ok(InterruptHandle::new(self.inner.interrupt.clone()))
+    }
+
+    pub fn is_execution_disabled(&self) -> Result<bool> {
+        let mut disabled = false;
+        unsafe {
+            ok_msg(
+                sys::JsIsRuntimeExecutionDisabled(self.raw(), &mut disabled),
+                "JsIsRuntimeExecutionDisabled failed",
+            )?;
+        }
+        Ok(disabled)
+    }
+
+    // Allow script to run again after an interrupt.
+    pub fn enable_execution(&self) -> Result<()> {
+        unsafe {
+            ok_msg(sys::JsEnableRuntimeExecution(self.raw()), "JsEnableRuntimeExecution failed")?;
+            exception::discard_pending();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn watchdog(&self) -> &Watchdog {
+        self.inner.watchdog.get_or_init(|| Watchdog::spawn(self.inner.interrupt.clone()))
+    }
+
+    pub(crate) fn raw(&self) -> sys::JsRuntimeHandle {
+        self.inner.raw
+    }
+
+    // For state the engine hands back to callbacks. It does not keep the runtime alive.
+    pub(crate) fn handle(&self) -> rc::Weak<RuntimeInner> {
+        Rc::downgrade(&self.inner)
+    }
+
+    // A temporary handle for the duration of a callback; None once disposal has begun.
+    pub(crate) fn from_handle(handle: &rc::Weak<RuntimeInner>) -> Option<Runtime> {
+        handle.upgrade().map(|inner| Runtime { inner })
+    }
+
+    pub(crate) fn liveness(&self) -> Weak<RuntimeShared> {
+        Arc::downgrade(&self.inner.alive)
+    }
+
+    // With `RuntimeBuilder::check_thread(true)`, fails with JsErrorWrongThread when
+    // called from any thread but the owning one. Always Ok otherwise. A debug
+    // assertion: safe code cannot reach a Runtime from another thread (it is not
+    // Send), so this only trips after unsafe code has moved one without
+    // `into_sendable`.
+    pub fn check_thread(&self) -> Result<()> {
+        if self.inner.check_thread && !self.inner.alive.on_owner_thread() {
+            return Err(err_msg(
+                JsErrorCode::JsErrorWrongThread,
+                "runtime used from a thread other than its owner".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Package the runtime for another thread. Fails with JsErrorRuntimeInUse if one
+    // of its contexts is still current on this thread (e.g. a leaked Guard).
+    pub fn into_sendable(self) -> Result<SendableRuntime> {
+        self.check_thread()?;
+        let mut current: sys::JsContextRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsGetCurrentContext(&mut current))?; }
+        if !current.is_null() {
+            let mut owner: sys::JsRuntimeHandle = std::ptr::null_mut();
+            unsafe { ok(sys::JsGetRuntime(current, &mut owner))?; }
+            if owner == self.raw() {
+                return Err(err_msg(
+                    JsErrorCode::JsErrorRuntimeInUse,
+                    "a context of this runtime is still current".to_string(),
+                ));
+            }
+        }
+        Ok(SendableRuntime { runtime: self })
+    }
+
+    // Register a callback_state pointer (allocated as Box<CallbackState> -> thin pointer)
+    pub(crate) fn register_callback_state(&self, p: *mut c_void) {
+        self.inner.callback_states.borrow_mut().insert(p);
+    }
+
+    // True if `p` was registered, in which case the caller now owns it.
+    pub(crate) fn unregister_callback_state(&self, p: *mut c_void) -> bool {
+        self.inner.callback_states.borrow_mut().remove(&p)
+    }
+
+    pub(crate) fn register_instance(&self, object: sys::JsValueRef) {
+        self.inner.instances.borrow_mut().insert(object as usize);
+    }
+
+    pub(crate) fn unregister_instance(&self, object: usize) {
+        self.inner.instances.borrow_mut().remove(&object);
+    }
+
+    pub(crate) fn is_instance(&self, object: sys::JsValueRef) -> bool {
+        self.inner.instances.borrow().contains(&(object as usize))
+    }
+
+    // Queue `value` to be dropped outside of any engine callback.
+    pub(crate) fn defer_drop(&self, value: Box<dyn Any>) {
+        self.inner.deferred_drops.borrow_mut().push(value);
+    }
+
+    pub(crate) fn drop_deferred(&self) {
+        self.inner.drop_deferred();
+    }
+}
+
+impl RuntimeInner {
+    fn drop_deferred(&self) {
+        // A drop may queue more (a closure holding the last handle to a function).
+        loop {
+            let batch = std::mem::take(&mut *self.deferred_drops.borrow_mut());
+            if batch.is_empty() {
+                break;
+            }
+            for value in batch {
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(move || drop(value))) {
+                    apply_panic_policy(self.panic_policy.get(), "dropping callback state", &*payload);
+                }
+            }
+        }
+    }
+}
+
+// Runtime configuration. Attributes are fixed once the runtime is created.
+//
+//     let runtime = Runtime::builder()
+//         .disable_eval(true)
+//         .disable_native_code_generation(true)
+//         .build()?;
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuntimeBuilder {
+    attributes: u32,
+    panic_policy: PanicPolicy,
+    memory_limit: Option<usize>,
+    check_thread: bool,
+}
+
+macro_rules! attribute_setters {
+    ($($name:ident => $flag:ident,)*) => {$(
+        pub fn $name(self, on: bool) -> Self {
+            self.flag(sys::JsRuntimeAttributes::$flag, on)
+        }
+    )*};
+}
+
+impl RuntimeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn flag(mut self, flag: sys::JsRuntimeAttributes, on: bool) -> Self {
+        if on {
+            self.attributes |= flag.0;
+        } else {
+            self.attributes &= !flag.0;
+        }
+        self
+    }
+
+    attribute_setters! {
+        disable_background_work => JsRuntimeAttributeDisableBackgroundWork,
+        allow_script_interrupt => JsRuntimeAttributeAllowScriptInterrupt,
+        enable_idle_processing => JsRuntimeAttributeEnableIdleProcessing,
+        // Turns off the JIT; everything runs in the interpreter.
+        disable_native_code_generation => JsRuntimeAttributeDisableNativeCodeGeneration,
+        // Makes `eval` and `Function(...)` throw.
+        disable_eval => JsRuntimeAttributeDisableEval,
+        enable_experimental_features => JsRuntimeAttributeEnableExperimentalFeatures,
+        dispatch_set_exceptions_to_debugger => JsRuntimeAttributeDispatchSetExceptionsToDebugger,
+        disable_fatal_on_oom => JsRuntimeAttributeDisableFatalOnOOM,
+        disable_executable_page_allocation => JsRuntimeAttributeDisableExecutablePageAllocation,
+    }
+
+    // Replace the whole attribute set at once.
+    pub fn attributes(mut self, attributes: sys::JsRuntimeAttributes) -> Self {
+        self.attributes = attributes.0;
+        self
+    }
+
+    pub fn panic_policy(mut self, policy: PanicPolicy) -> Self {
+        self.panic_policy = policy;
+        self
+    }
+
+    // See `Runtime::set_memory_limit`.
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    // Record the creating thread and reject use from any other (see `Runtime::check_thread`;
+    // a debug assertion only).
+    pub fn check_thread(mut self, on: bool) -> Self {
+        self.check_thread = on;
+        self
+    }
+
+    pub fn build(self) -> Result<Runtime> {
         let mut rt: sys::JsRuntimeHandle = std::ptr::null_mut();
         unsafe {
             ok(sys::JsCreateRuntime(
-                sys::JsRuntimeAttributes::None,
-                std::ptr::null_mut(),
+                sys::JsRuntimeAttributes(self.attributes),
+                None,
                 &mut rt,
             ))?;
         }
 
-        Ok(Self {
+        let runtime = Runtime { inner: Rc::new(RuntimeInner {
             raw: rt,
-            callback_states: Arc::new(Mutex::new(Vec::new())),
-        })
+            callback_states: RefCell::new(HashSet::new()),
+            instances: RefCell::new(HashSet::new()),
+            panic_policy: Cell::new(self.panic_policy),
+            attributes: sys::JsRuntimeAttributes(self.attributes),
+            interrupt: InterruptTarget::new(rt),
+            watchdog: OnceCell::new(),
+            alive: Arc::new(RuntimeShared { owner: Mutex::new(Some(thread::current().id())) }),
+            check_thread: self.check_thread,
+            memory_callback: RefCell::new(None),
+            before_collect_callback: RefCell::new(None),
+            retired_callbacks: RefCell::new(Vec::new()),
+            deferred_drops: RefCell::new(Vec::new()),
+            module_hosts: RefCell::new(Vec::new()),
+        }) };
+        RUNTIMES.lock().unwrap_or_else(|e| e.into_inner()).push((rt as usize, runtime.liveness()));
+        if self.memory_limit.is_some() {
+            runtime.set_memory_limit(self.memory_limit)?;
+        }
+        Ok(runtime)
     }
+}
 
-    // Register a callback_state pointer (allocated as Box<CallbackState> -> thin pointer)
-    pub(crate) fn register_callback_state(&self, p: *mut c_void) {
-        self.callback_states.lock().unwrap().push(p);
+// A runtime in transit between threads. Only obtainable while none of its
+// contexts is current, and all of its callbacks are Send, so moving it is sound.
+// The only other references to its inner state are the weak ones in engine-owned
+// callback state, which travels with it.
+pub struct SendableRuntime {
+    runtime: Runtime,
+}
+
+unsafe impl Send for SendableRuntime {}
+
+impl SendableRuntime {
+    // Unpack on the receiving thread, which becomes the new owner.
+    pub fn into_runtime(self) -> Runtime {
+        *self.runtime.inner.alive.owner.lock().unwrap_or_else(|e| e.into_inner()) = Some(thread::current().id());
+        self.runtime
     }
 }
 
-impl Drop for Runtime {
+impl Drop for RuntimeInner {
     fn drop(&mut self) {
+        // Outstanding interrupt handles must not reach the runtime past this point.
+        self.interrupt.clear();
+        // Neither must persistent values dropped along with the callback states.
+        *self.alive.owner.lock().unwrap_or_else(|e| e.into_inner()) = None;
+
         if !self.raw.is_null() {
+            let raw = self.raw as usize;
+            RUNTIMES.lock().unwrap_or_else(|e| e.into_inner()).retain(|(r, _)| *r != raw);
             unsafe {
                 let _ = sys::JsDisposeRuntime(self.raw);
             }
@@ -43,9 +403,17 @@ impl Drop for Runtime {
         }
 
         // After disposing the runtime, it is safe to free callback states.
-        let mut reg = self.callback_states.lock().unwrap();
-        for p in reg.drain(..) {
-            unsafe { crate::value::free_callback_state(p); }
+        let states: Vec<*mut c_void> = self.callback_states.borrow_mut().drain().collect();
+        let hosts: Vec<*mut ModuleHost> = self.module_hosts.borrow_mut().drain(..).collect();
+        {
+            let mut deferred = self.deferred_drops.borrow_mut();
+            for p in states {
+                deferred.push(unsafe { crate::value::take_callback_state(p) });
+            }
+            for host in hosts {
+                deferred.push(unsafe { Box::from_raw(host) });
+            }
         }
+        self.drop_deferred();
     }
 }
@@ -2,25 +2,41 @@ use crate::error::{ok, Result};
 use crate::guard::Guard;
 use crate::value::Value;
 use catswords_jsrt_sys as sys;
+use std::time::Duration;
 
-fn to_wide_null(s: &str) -> Vec<u16> {
-    let mut v: Vec<u16> = s.encode_utf16().collect();
-    v.push(0);
-    v
-}
-
-pub fn eval(_guard: &Guard<'_>, code: &str) -> Result<Value> {
-    let script = to_wide_null(code);
-    let url = to_wide_null("eval.js");
+pub fn eval<'g>(guard: &'g Guard<'_>, code: &str) -> Result<Value<'g>> {
+    let script = Value::string_utf8(guard, code)?;
+    let url = Value::string_utf8(guard, "eval.js")?;
 
     let mut out: sys::JsValueRef = std::ptr::null_mut();
     unsafe {
-        ok(sys::JsRunScript(
-            script.as_ptr(),
+        ok(sys::JsRun(
+            script.raw(),
             0 as sys::JsSourceContext,
-            url.as_ptr(),
+            url.raw(),
+            sys::JsParseScriptAttributes::JsParseScriptAttributeNone,
             &mut out,
         ))?;
     }
-    Ok(Value { raw: out })
+    Ok(Value::from_raw(out))
+}
+
+// Like `eval`, but the runtime's watchdog thread interrupts the script once
+// `timeout` has elapsed, failing with `Error::ScriptTerminated`. The runtime must
+// allow script interrupts. If the watchdog fired, execution is re-enabled before
+// returning; an interrupt from some other `InterruptHandle` is left in place.
+pub fn eval_with_timeout<'g>(guard: &'g Guard<'_>, code: &str, timeout: Duration) -> Result<Value<'g>> {
+    let runtime = guard.runtime();
+    runtime.interrupt_handle()?;
+
+    let watchdog = runtime.watchdog();
+    let id = watchdog.arm(timeout);
+    let result = eval(guard, code);
+
+    // Even a late interrupt (fired just as the script finished) is ours to undo.
+    // Best effort: the caller needs `result`, not a failure to re-enable.
+    if watchdog.disarm(id) && runtime.is_execution_disabled().unwrap_or(true) {
+        let _ = runtime.enable_execution();
+    }
+    result
 }
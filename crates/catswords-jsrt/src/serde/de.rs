@@ -0,0 +1,303 @@
+use crate::error::{type_err, Error, Result};
+use crate::guard::Guard;
+use crate::value::{within, Array, FromJs, Object, Value};
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+use catswords_jsrt_sys::JsValueType;
+
+// Reads a Rust value out of any JavaScript value. Numbers with an integral
+// value are offered to the visitor as integers, everything else as f64.
+pub struct Deserializer<'g> {
+    guard: &'g Guard<'g>,
+    value: Value<'g>,
+}
+
+impl<'g> Deserializer<'g> {
+    pub fn new(guard: &'g Guard<'_>, value: Value<'g>) -> Self {
+        Self { guard, value }
+    }
+
+    fn at(&self, value: Value<'g>) -> Self {
+        Self { guard: self.guard, value }
+    }
+
+    fn is_nullish(&self) -> bool {
+        self.value.is_undefined(self.guard) || self.value.is_null(self.guard)
+    }
+
+    fn bytes(&self) -> Result<Option<Vec<u8>>> {
+        let guard = self.guard;
+        let bytes = match self.value.value_type(guard)? {
+            JsValueType::JsArrayBuffer => self.value.as_array_buffer(guard).map(|b| b.to_vec(guard)),
+            JsValueType::JsTypedArray => self.value.as_typed_array(guard).map(|a| a.to_byte_vec(guard)),
+            JsValueType::JsDataView => self.value.as_data_view(guard).map(|v| v.to_vec(guard)),
+            _ => None,
+        };
+        bytes.transpose()
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let guard = self.guard;
+        match self.value.value_type(guard)? {
+            JsValueType::JsUndefined | JsValueType::JsNull => visitor.visit_unit(),
+            JsValueType::JsBoolean => visitor.visit_bool(bool::from_js(guard, &self.value)?),
+            JsValueType::JsNumber => {
+                let n = f64::from_js(guard, &self.value)?;
+                // i64::MAX as f64 rounds up to 2^63, the exclusive bound; likewise for u64.
+                if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+                    visitor.visit_i64(n as i64)
+                } else if n.fract() == 0.0 && n >= 0.0 && n < u64::MAX as f64 {
+                    visitor.visit_u64(n as u64)
+                } else {
+                    visitor.visit_f64(n)
+                }
+            }
+            JsValueType::JsString => visitor.visit_string(String::from_js(guard, &self.value)?),
+            JsValueType::JsArray => {
+                let array = self.value.as_array(guard).ok_or_else(|| type_err("expected an array".to_string()))?;
+                let len = array.len(guard)?;
+                visitor.visit_seq(SeqAccess { de: self, array, index: 0, len })
+            }
+            JsValueType::JsArrayBuffer | JsValueType::JsTypedArray | JsValueType::JsDataView => {
+                visitor.visit_byte_buf(self.bytes()?.unwrap_or_default())
+            }
+            JsValueType::JsObject | JsValueType::JsError => {
+                let object = self.value.as_object(guard).ok_or_else(|| type_err("expected an object".to_string()))?;
+                let keys = object.get_own_property_names(guard)?;
+                let keys = Vec::<String>::from_js(guard, keys.as_value())?;
+                visitor.visit_map(MapAccess { de: self, object, keys: keys.into_iter(), key: None })
+            }
+            _ => {
+                Err(type_err(format!("cannot deserialize a {}", self.value.type_name(guard))))
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_nullish() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.bytes()? {
+            Some(bytes) => visitor.visit_byte_buf(bytes),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // A variant name, or an object with the variant name as its only key.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let guard = self.guard;
+        if self.value.as_string(guard).is_some() {
+            let variant = String::from_js(guard, &self.value)?;
+            return visitor.visit_enum(EnumAccess { de: self, variant, payload: None });
+        }
+        let object = self.value.as_object(guard).ok_or_else(|| {
+            type_err(format!("expected a {} variant, got {}", name, self.value.type_name(guard)))
+        })?;
+        let keys = object.get_own_property_names(guard)?;
+        let keys = Vec::<String>::from_js(guard, keys.as_value())?;
+        let [variant] = <[String; 1]>::try_from(keys).map_err(|keys| {
+            type_err(format!("expected an object with a single {} variant key, got {} keys", name, keys.len()))
+        })?;
+        let payload = object.get(guard, &variant)?;
+        let context = format!("variant {:?}", variant);
+        visitor
+            .visit_enum(EnumAccess { de: self.at(payload), variant, payload: Some(payload) })
+            .map_err(|e| within(e, context))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqAccess<'g> {
+    de: Deserializer<'g>,
+    array: Array<'g>,
+    index: u32,
+    len: u32,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        let index = self.index;
+        self.index += 1;
+        let value = self.array.get(self.de.guard, index)?;
+        seed.deserialize(self.de.at(value))
+            .map(Some)
+            .map_err(|e| within(e, format_args!("element {}", index)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some((self.len - self.index) as usize)
+    }
+}
+
+struct MapAccess<'g> {
+    de: Deserializer<'g>,
+    object: Object<'g>,
+    keys: std::vec::IntoIter<String>,
+    key: Option<String>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some(key) = self.keys.next() else {
+            return Ok(None);
+        };
+        let out = seed.deserialize(KeyDeserializer { key: key.clone() })?;
+        self.key = Some(key);
+        Ok(Some(out))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let key = self.key.take().ok_or_else(|| type_err("map value requested before its key".to_string()))?;
+        let value = self.object.get(self.de.guard, &key)?;
+        seed.deserialize(self.de.at(value)).map_err(|e| within(e, format_args!("property {:?}", key)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len())
+    }
+}
+
+// Property names are always strings; numeric map keys are parsed back.
+struct KeyDeserializer {
+    key: String,
+}
+
+macro_rules! parse_key {
+    ($($method:ident => $visit:ident: $t:ty),*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            match self.key.parse::<$t>() {
+                Ok(n) => visitor.$visit(n),
+                Err(_) => visitor.visit_string(self.key),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.key)
+    }
+
+    parse_key! {
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self.key.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess<'g> {
+    de: Deserializer<'g>,
+    variant: String,
+    payload: Option<Value<'g>>,
+}
+
+impl<'de, 'g> de::EnumAccess<'de> for EnumAccess<'g> {
+    type Error = Error;
+    type Variant = VariantAccess<'g>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, VariantAccess<'g>)> {
+        let variant = seed.deserialize(KeyDeserializer { key: self.variant })?;
+        Ok((variant, VariantAccess { de: self.de, payload: self.payload }))
+    }
+}
+
+struct VariantAccess<'g> {
+    de: Deserializer<'g>,
+    payload: Option<Value<'g>>,
+}
+
+impl<'g> VariantAccess<'g> {
+    fn payload(self) -> Result<Deserializer<'g>> {
+        match self.payload {
+            Some(_) => Ok(self.de),
+            None => Err(type_err("expected an object holding the variant's data, got its name".to_string())),
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.payload {
+            Some(_) if !self.de.is_nullish() => Err(type_err("unit variant does not take data".to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.payload()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self.payload()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self.payload()?, visitor)
+    }
+}
@@ -0,0 +1,73 @@
+// serde support (the `serde` feature): any `Serialize` type becomes a plain
+// JavaScript value and any value can be read back into a `Deserialize` type,
+// without going through JSON text.
+//
+// Structs and maps become objects (map keys must be strings or numbers),
+// sequences and tuples arrays, bytes a Uint8Array, `None` null and `()`
+// undefined. Enums use the same shape as `#[derive(FromJs, IntoJs)]`: unit
+// variants are their name, the others `{ Variant: payload }`.
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+use crate::error::{type_err, Error, Result};
+use crate::guard::Guard;
+use crate::value::{FromJs, IntoJs, Value};
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+use std::fmt::Display;
+use std::ops::{Deref, DerefMut};
+
+pub fn to_value<'g, T: Serialize + ?Sized>(guard: &'g Guard<'_>, value: &T) -> Result<Value<'g>> {
+    value.serialize(Serializer::new(guard))
+}
+
+pub fn from_value<T: DeserializeOwned>(guard: &Guard<'_>, value: &Value<'_>) -> Result<T> {
+    T::deserialize(Deserializer::new(guard, *value))
+}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        type_err(msg.to_string())
+    }
+}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        type_err(msg.to_string())
+    }
+}
+
+// Converts a serde type through `FromJs` / `IntoJs`, so it can be a parameter or
+// the return value of a `Function::wrap` closure.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Serde<T>(pub T);
+
+impl<T> Deref for Serde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Serde<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<'g, T: Serialize> IntoJs<'g> for Serde<T> {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        to_value(guard, &self.0)
+    }
+}
+
+impl<'g, T: DeserializeOwned> FromJs<'g> for Serde<T> {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        from_value(guard, value).map(Serde)
+    }
+}
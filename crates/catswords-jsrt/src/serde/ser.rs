@@ -0,0 +1,325 @@
+use crate::error::{type_err, Error, Result};
+use crate::guard::Guard;
+use crate::value::{Array, ArrayBuffer, IntoJs, Object, TypedArray, Value};
+use ::serde::ser::{self, Serialize};
+use catswords_jsrt_sys::{JsTypedArrayType, JsValueType};
+
+// Serializes into a fresh JavaScript value.
+#[derive(Clone, Copy)]
+pub struct Serializer<'g> {
+    guard: &'g Guard<'g>,
+}
+
+impl<'g> Serializer<'g> {
+    pub fn new(guard: &'g Guard<'_>) -> Self {
+        Self { guard }
+    }
+
+    fn value<T: Serialize + ?Sized>(&self, value: &T) -> Result<Value<'g>> {
+        value.serialize(*self)
+    }
+
+    fn wrap_variant(&self, variant: &'static str, payload: Value<'g>) -> Result<Value<'g>> {
+        let object = Object::new(self.guard)?;
+        object.set(self.guard, variant, &payload)?;
+        Ok(object.into())
+    }
+}
+
+impl<'g> ser::Serializer for Serializer<'g> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer<'g>;
+    type SerializeTuple = ArraySerializer<'g>;
+    type SerializeTupleStruct = ArraySerializer<'g>;
+    type SerializeTupleVariant = VariantSerializer<ArraySerializer<'g>>;
+    type SerializeMap = MapSerializer<'g>;
+    type SerializeStruct = ObjectSerializer<'g>;
+    type SerializeStructVariant = VariantSerializer<ObjectSerializer<'g>>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value<'g>> {
+        v.into_js(self.guard)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value<'g>> {
+        Value::string_utf8(self.guard, v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value<'g>> {
+        Value::string_utf8(self.guard, v)
+    }
+
+    // A Uint8Array over a copy of the bytes.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value<'g>> {
+        let buffer = ArrayBuffer::from_vec(self.guard, v.to_vec())?;
+        let len = u32::try_from(v.len()).map_err(|_| type_err(format!("{} bytes is too large", v.len())))?;
+        Ok(TypedArray::new(self.guard, JsTypedArrayType::JsArrayTypeUint8, &buffer, 0, len)?.into())
+    }
+
+    fn serialize_none(self) -> Result<Value<'g>> {
+        Value::null(self.guard)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value<'g>> {
+        self.value(value)
+    }
+
+    fn serialize_unit(self) -> Result<Value<'g>> {
+        Value::undefined(self.guard)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value<'g>> {
+        Value::undefined(self.guard)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Value<'g>> {
+        Value::string_utf8(self.guard, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Value<'g>> {
+        self.value(value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value<'g>> {
+        let payload = self.value(value)?;
+        self.wrap_variant(variant, payload)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ArraySerializer<'g>> {
+        ArraySerializer::new(self, len.unwrap_or(0))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ArraySerializer<'g>> {
+        ArraySerializer::new(self, len)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<ArraySerializer<'g>> {
+        ArraySerializer::new(self, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(VariantSerializer { variant, inner: ArraySerializer::new(self, len)? })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer<'g>> {
+        Ok(MapSerializer { inner: ObjectSerializer::new(self)?, key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<ObjectSerializer<'g>> {
+        ObjectSerializer::new(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(VariantSerializer { variant, inner: ObjectSerializer::new(self)? })
+    }
+}
+
+pub struct ArraySerializer<'g> {
+    ser: Serializer<'g>,
+    array: Array<'g>,
+    index: u32,
+}
+
+impl<'g> ArraySerializer<'g> {
+    fn new(ser: Serializer<'g>, len: usize) -> Result<Self> {
+        let len = u32::try_from(len).map_err(|_| type_err(format!("{} elements is too many for an array", len)))?;
+        Ok(Self { ser, array: Array::new(ser.guard, len)?, index: 0 })
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = self.ser.value(value)?;
+        self.array.set(self.ser.guard, self.index, &value)?;
+        self.index += 1;
+        Ok(())
+    }
+}
+
+impl<'g> ser::SerializeSeq for ArraySerializer<'g> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        Ok(self.array.into())
+    }
+}
+
+impl<'g> ser::SerializeTuple for ArraySerializer<'g> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        Ok(self.array.into())
+    }
+}
+
+impl<'g> ser::SerializeTupleStruct for ArraySerializer<'g> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        Ok(self.array.into())
+    }
+}
+
+pub struct ObjectSerializer<'g> {
+    ser: Serializer<'g>,
+    object: Object<'g>,
+}
+
+impl<'g> ObjectSerializer<'g> {
+    fn new(ser: Serializer<'g>) -> Result<Self> {
+        Ok(Self { ser, object: Object::new(ser.guard)? })
+    }
+}
+
+impl<'g> ser::SerializeStruct for ObjectSerializer<'g> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        let value = self.ser.value(value)?;
+        self.object.set(self.ser.guard, key, &value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        Ok(self.object.into())
+    }
+}
+
+pub struct MapSerializer<'g> {
+    inner: ObjectSerializer<'g>,
+    key: Option<String>,
+}
+
+impl<'g> ser::SerializeMap for MapSerializer<'g> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    // Keys become property names, so only strings and numbers are accepted.
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let guard = self.inner.ser.guard;
+        let key = self.inner.ser.value(key)?;
+        match key.value_type(guard)? {
+            JsValueType::JsString | JsValueType::JsNumber => {
+                self.key = Some(key.coerce_to_string(guard)?);
+                Ok(())
+            }
+            _ => Err(type_err(format!("map keys must be strings or numbers, got {}", key.type_name(guard)))),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.key.take().ok_or_else(|| type_err("map value serialized before its key".to_string()))?;
+        let value = self.inner.ser.value(value)?;
+        self.inner.object.set(self.inner.ser.guard, &key, &value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        Ok(self.inner.object.into())
+    }
+}
+
+// `{ variant: payload }` around an array or object serializer.
+pub struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<'g> ser::SerializeTupleVariant for VariantSerializer<ArraySerializer<'g>> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        let ser = self.inner.ser;
+        ser.wrap_variant(self.variant, self.inner.array.into())
+    }
+}
+
+impl<'g> ser::SerializeStructVariant for VariantSerializer<ObjectSerializer<'g>> {
+    type Ok = Value<'g>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value<'g>> {
+        let ser = self.inner.ser;
+        ser.wrap_variant(self.variant, self.inner.object.into())
+    }
+}
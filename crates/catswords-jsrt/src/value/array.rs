@@ -0,0 +1,57 @@
+use crate::error::{ok, ok_msg, Result};
+use crate::guard::Guard;
+use crate::value::{JsIterator, Object, Value};
+use catswords_jsrt_sys as sys;
+
+pub struct Array<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> Array<'g> {
+    pub fn new(_guard: &'g Guard<'_>, length: u32) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsCreateArray(length, &mut out), "JsCreateArray failed")?; }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    pub fn len(&self, guard: &Guard<'_>) -> Result<u32> {
+        let length = Object::from_value(self.v).get(guard, "length")?;
+        let mut out: f64 = 0.0;
+        unsafe { ok(sys::JsNumberToDouble(length.raw, &mut out))?; }
+        Ok(out as u32)
+    }
+
+    pub fn is_empty(&self, guard: &Guard<'_>) -> Result<bool> {
+        Ok(self.len(guard)? == 0)
+    }
+
+    pub fn get(&self, guard: &Guard<'_>, index: u32) -> Result<Value<'g>> {
+        Object::from_value(self.v).get_index(guard, index)
+    }
+
+    pub fn set(&self, guard: &Guard<'_>, index: u32, value: &Value<'_>) -> Result<()> {
+        Object::from_value(self.v).set_index(guard, index, value)
+    }
+
+    // `array.push(value)`.
+    pub fn push(&self, guard: &Guard<'_>, value: &Value<'_>) -> Result<()> {
+        let len = self.len(guard)?;
+        self.set(guard, len, value)
+    }
+
+    pub fn iter<'a, 'rt>(&self, guard: &'a Guard<'rt>) -> Result<JsIterator<'a, 'rt>> {
+        JsIterator::new(guard, &self.v)
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
@@ -1,13 +1,28 @@
-use crate::error::{ok, ok_msg, Result};
+use crate::error::{err_msg, ok, ok_msg, type_err, Result};
 use crate::guard::Guard;
+use crate::value::number::checked_integer;
+use crate::value::{
+    Array, ArrayBuffer, Boolean, DataView, Function, JsIterator, JsString, Number, Object, Symbol, TypedArray,
+};
 use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::{JsErrorCode, JsValueType};
+use std::ffi::c_char;
+use std::marker::PhantomData;
 
-#[derive(Clone, Copy)]
-pub struct Value {
+// A JavaScript value, valid for as long as the `Guard` it came from is borrowed.
+// That keeps it from outliving the guard, its runtime or the runtime's disposal;
+// use `PersistentValue` to keep a value beyond that.
+#[derive(Clone, Copy, Debug)]
+pub struct Value<'g> {
     pub(crate) raw: sys::JsValueRef,
+    _guard: PhantomData<&'g ()>,
 }
 
-impl Value {
+impl<'g> Value<'g> {
+    pub(crate) fn from_raw(raw: sys::JsValueRef) -> Self {
+        Self { raw, _guard: PhantomData }
+    }
+
     pub fn raw(&self) -> sys::JsValueRef {
         self.raw
     }
@@ -21,41 +36,216 @@ impl Value {
         Ok(out)
     }
 
-    pub fn undefined(_guard: &Guard<'_>) -> Result<Self> {
+    // Fails with `Error::Type` unless the value is a number; see `coerce_to_f64`.
+    pub fn to_f64(&self, guard: &Guard<'_>) -> Result<f64> {
+        self.expect_number(guard)?.value(guard)
+    }
+
+    // Checked conversions: fail on fractional, non-finite or out-of-range numbers
+    // instead of truncating like `to_integer`.
+    pub fn to_i64(&self, guard: &Guard<'_>) -> Result<i64> {
+        let n = checked_integer(
+            self.to_f64(guard)?,
+            -9_223_372_036_854_775_808.0,
+            9_223_372_036_854_775_808.0,
+            "i64",
+        )?;
+        Ok(n as i64)
+    }
+
+    pub fn to_u32(&self, guard: &Guard<'_>) -> Result<u32> {
+        Ok(checked_integer(self.to_f64(guard)?, 0.0, 4_294_967_296.0, "u32")? as u32)
+    }
+
+    pub fn to_u64(&self, guard: &Guard<'_>) -> Result<u64> {
+        let n = checked_integer(self.to_f64(guard)?, 0.0, 18_446_744_073_709_551_616.0, "u64")?;
+        Ok(n as u64)
+    }
+
+    // Runs JavaScript ToNumber first, so strings, booleans etc. are accepted.
+    pub fn coerce_to_f64(&self, guard: &Guard<'_>) -> Result<f64> {
+        Number::coerce(guard, self)?.value(guard)
+    }
+
+    // Values are tied to a guard's lifetime, not to its context, so an object of
+    // one context can be handed to a guard of another context of the same
+    // runtime. ChakraCore marshals it across, which is sound, but script then
+    // sees a foreign object. This fails with JsErrorInvalidContext for such an
+    // object; primitives belong to no context and always pass.
+    pub fn check_context(&self, guard: &Guard<'_>) -> Result<()> {
+        if self.as_object(guard).is_none() {
+            return Ok(());
+        }
+        let mut context: sys::JsContextRef = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsGetContextOfObject(self.raw, &mut context), "JsGetContextOfObject failed")?; }
+        if context != guard.context_raw() {
+            return Err(err_msg(
+                JsErrorCode::JsErrorInvalidContext,
+                "value belongs to another context than the guard's".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn value_type(&self, _guard: &Guard<'_>) -> Result<JsValueType> {
+        let mut out = JsValueType::JsUndefined;
+        unsafe { ok(sys::JsGetValueType(self.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    fn is_type(&self, guard: &Guard<'_>, types: &[JsValueType]) -> bool {
+        self.value_type(guard).map(|t| types.contains(&t)).unwrap_or(false)
+    }
+
+    // Lower-case JavaScript-style name of the value's type, for error messages.
+    pub fn type_name(&self, guard: &Guard<'_>) -> &'static str {
+        match self.value_type(guard) {
+            Ok(JsValueType::JsUndefined) => "undefined",
+            Ok(JsValueType::JsNull) => "null",
+            Ok(JsValueType::JsNumber) => "number",
+            Ok(JsValueType::JsString) => "string",
+            Ok(JsValueType::JsBoolean) => "boolean",
+            Ok(JsValueType::JsObject) => "object",
+            Ok(JsValueType::JsFunction) => "function",
+            Ok(JsValueType::JsError) => "error",
+            Ok(JsValueType::JsArray) => "array",
+            Ok(JsValueType::JsSymbol) => "symbol",
+            Ok(JsValueType::JsArrayBuffer) => "ArrayBuffer",
+            Ok(JsValueType::JsTypedArray) => "TypedArray",
+            Ok(JsValueType::JsDataView) => "DataView",
+            Ok(_) | Err(_) => "unknown",
+        }
+    }
+
+    pub fn is_undefined(&self, guard: &Guard<'_>) -> bool {
+        self.is_type(guard, &[JsValueType::JsUndefined])
+    }
+
+    pub fn is_null(&self, guard: &Guard<'_>) -> bool {
+        self.is_type(guard, &[JsValueType::JsNull])
+    }
+
+    // Checked downcasts. Arrays, functions, errors and buffers are objects too.
+    pub fn as_object(&self, guard: &Guard<'_>) -> Option<Object<'g>> {
+        let object_types = [
+            JsValueType::JsObject,
+            JsValueType::JsFunction,
+            JsValueType::JsError,
+            JsValueType::JsArray,
+            JsValueType::JsArrayBuffer,
+            JsValueType::JsTypedArray,
+            JsValueType::JsDataView,
+        ];
+        self.is_type(guard, &object_types).then(|| Object::from_value(*self))
+    }
+
+    pub fn as_array(&self, guard: &Guard<'_>) -> Option<Array<'g>> {
+        self.is_type(guard, &[JsValueType::JsArray]).then(|| Array::from_value(*self))
+    }
+
+    pub fn as_string(&self, guard: &Guard<'_>) -> Option<JsString<'g>> {
+        self.is_type(guard, &[JsValueType::JsString]).then(|| JsString::from_value(*self))
+    }
+
+    pub fn as_boolean(&self, guard: &Guard<'_>) -> Option<Boolean<'g>> {
+        self.is_type(guard, &[JsValueType::JsBoolean]).then(|| Boolean::from_value(*self))
+    }
+
+    pub fn as_symbol(&self, guard: &Guard<'_>) -> Option<Symbol<'g>> {
+        self.is_type(guard, &[JsValueType::JsSymbol]).then(|| Symbol::from_value(*self))
+    }
+
+    pub fn as_number(&self, guard: &Guard<'_>) -> Option<Number<'g>> {
+        self.is_type(guard, &[JsValueType::JsNumber]).then(|| Number::from_value(*self))
+    }
+
+    pub fn as_function(&self, guard: &Guard<'_>) -> Option<Function<'g>> {
+        self.is_type(guard, &[JsValueType::JsFunction]).then(|| Function::from_value(*self))
+    }
+
+    // Iterate any iterable value via `Symbol.iterator`.
+    pub fn iter<'a, 'rt>(&self, guard: &'a Guard<'rt>) -> Result<JsIterator<'a, 'rt>> {
+        JsIterator::new(guard, self)
+    }
+
+    pub fn as_array_buffer(&self, guard: &Guard<'_>) -> Option<ArrayBuffer<'g>> {
+        self.is_type(guard, &[JsValueType::JsArrayBuffer]).then(|| ArrayBuffer::from_value(*self))
+    }
+
+    pub fn as_typed_array(&self, guard: &Guard<'_>) -> Option<TypedArray<'g>> {
+        self.is_type(guard, &[JsValueType::JsTypedArray]).then(|| TypedArray::from_value(*self))
+    }
+
+    pub fn as_data_view(&self, guard: &Guard<'_>) -> Option<DataView<'g>> {
+        self.is_type(guard, &[JsValueType::JsDataView]).then(|| DataView::from_value(*self))
+    }
+
+    fn expect_number(&self, guard: &Guard<'_>) -> Result<Number<'g>> {
+        match self.as_number(guard) {
+            Some(n) => Ok(n),
+            None => Err(type_err(format!("expected a number, got {}", self.type_name(guard)))),
+        }
+    }
+
+    fn expect_string(&self, guard: &Guard<'_>) -> Result<JsString<'g>> {
+        match self.as_string(guard) {
+            Some(s) => Ok(s),
+            None => Err(type_err(format!("expected a string, got {}", self.type_name(guard)))),
+        }
+    }
+
+    pub fn to_string_lossy(&self, guard: &Guard<'_>) -> Result<String> {
+        self.expect_string(guard)?.to_string_lossy(guard)
+    }
+
+    pub fn to_rust_string(&self, guard: &Guard<'_>) -> Result<String> {
+        self.expect_string(guard)?.to_rust_string(guard)
+    }
+
+    pub fn to_utf16(&self, guard: &Guard<'_>) -> Result<Vec<u16>> {
+        self.expect_string(guard)?.to_utf16(guard)
+    }
+
+    // Runs JavaScript ToString first, so numbers, objects etc. are accepted.
+    pub fn coerce_to_string(&self, guard: &Guard<'_>) -> Result<String> {
+        JsString::coerce(guard, self)?.to_string_lossy(guard)
+    }
+
+    pub fn undefined(_guard: &'g Guard<'_>) -> Result<Self> {
         let mut v: sys::JsValueRef = std::ptr::null_mut();
         unsafe { ok(sys::JsGetUndefinedValue(&mut v))?; }
-        Ok(Self { raw: v })
+        Ok(Self::from_raw(v))
     }
 
-    pub fn null(_guard: &Guard<'_>) -> Result<Self> {
+    pub fn null(_guard: &'g Guard<'_>) -> Result<Self> {
         let mut v: sys::JsValueRef = std::ptr::null_mut();
         unsafe { ok(sys::JsGetNullValue(&mut v))?; }
-        Ok(Self { raw: v })
+        Ok(Self::from_raw(v))
     }
 	
-    pub fn string_utf8(_guard: &Guard<'_>, s: &str) -> Result<Self> {
+    pub fn string_utf8(_guard: &'g Guard<'_>, s: &str) -> Result<Self> {
         let mut out: sys::JsValueRef = std::ptr::null_mut();
         unsafe {
-            ok_msg(sys::JsCreateString(s.as_ptr(), s.len(), &mut out), "JsCreateString failed")?;
+            ok_msg(sys::JsCreateString(s.as_ptr() as *const c_char, s.len(), &mut out), "JsCreateString failed")?;
         }
-        Ok(Self { raw: out })
+        Ok(Self::from_raw(out))
     }
 
-    pub fn error_from_message(guard: &Guard<'_>, msg: &str) -> Result<Self> {
+    pub fn error_from_message(guard: &'g Guard<'_>, msg: &str) -> Result<Self> {
         let message = Self::string_utf8(guard, msg)?;
         let mut out: sys::JsValueRef = std::ptr::null_mut();
         unsafe {
             ok_msg(sys::JsCreateError(message.raw, &mut out), "JsCreateError failed")?;
         }
-        Ok(Self { raw: out })
+        Ok(Self::from_raw(out))
     }
 
-    pub fn type_error_from_message(guard: &Guard<'_>, msg: &str) -> Result<Self> {
+    pub fn type_error_from_message(guard: &'g Guard<'_>, msg: &str) -> Result<Self> {
         let message = Self::string_utf8(guard, msg)?;
         let mut out: sys::JsValueRef = std::ptr::null_mut();
         unsafe {
             ok_msg(sys::JsCreateTypeError(message.raw, &mut out), "JsCreateTypeError failed")?;
         }
-        Ok(Self { raw: out })
+        Ok(Self::from_raw(out))
     }
 }
@@ -0,0 +1,34 @@
+use crate::error::{ok, Result};
+use crate::guard::Guard;
+use crate::value::Value;
+use catswords_jsrt_sys as sys;
+
+pub struct Boolean<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> Boolean<'g> {
+    pub fn new(_guard: &'g Guard<'_>, b: bool) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsBoolToBoolean(b, &mut out))?; }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    pub fn value(&self, _guard: &Guard<'_>) -> Result<bool> {
+        let mut out = false;
+        unsafe { ok(sys::JsBooleanToBool(self.v.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
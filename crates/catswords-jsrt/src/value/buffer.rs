@@ -0,0 +1,446 @@
+use crate::error::{ok, ok_msg, type_err, Result};
+use crate::guard::Guard;
+use crate::value::{Object, Value};
+use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::JsTypedArrayType;
+use std::ffi::c_void;
+
+// Views straight into engine memory are unsafe, shared ones included: wrappers
+// are cheap copies of the same value, and script can reach the buffer too, so
+// nothing stops another view from aliasing a slice, or script from writing to or
+// detaching the buffer under it. `copy_from_slice` and `to_vec` are the safe way
+// to move data in and out. Every access to a detached buffer is an error.
+
+unsafe fn bytes<'s>(data: *mut u8, len: u32) -> &'s [u8] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, len as usize)
+    }
+}
+
+unsafe fn bytes_mut<'s>(data: *mut u8, len: u32) -> &'s mut [u8] {
+    if data.is_null() || len == 0 {
+        &mut []
+    } else {
+        std::slice::from_raw_parts_mut(data, len as usize)
+    }
+}
+
+// Overlap is fine: `src` may be a view of the same buffer.
+unsafe fn copy_into<T: Copy>(data: *mut T, len: usize, src: &[T]) -> Result<()> {
+    if src.len() != len {
+        return Err(type_err(format!("source has {} elements, destination has {}", src.len(), len)));
+    }
+    if len > 0 {
+        std::ptr::copy(src.as_ptr(), data, len);
+    }
+    Ok(())
+}
+
+// A detached buffer reports no storage, just like an empty one, so only empty
+// results need the (slower) check.
+fn check_attached(guard: &Guard<'_>, buffer: ArrayBuffer<'_>, data: *mut u8, len: u32) -> Result<()> {
+    if (data.is_null() || len == 0) && buffer.is_detached(guard)? {
+        return Err(type_err("ArrayBuffer is detached".to_string()));
+    }
+    Ok(())
+}
+
+fn byte_len(len: usize) -> Result<u32> {
+    u32::try_from(len).map_err(|_| type_err(format!("{} bytes is too large for an ArrayBuffer", len)))
+}
+
+pub struct ArrayBuffer<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> ArrayBuffer<'g> {
+    // Zero-filled buffer owned by the engine.
+    pub fn new(_guard: &'g Guard<'_>, len: usize) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsCreateArrayBuffer(byte_len(len)?, &mut out), "JsCreateArrayBuffer failed")?; }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    // Hand a Rust buffer to script without copying. The engine frees it (drops
+    // the Box) when the ArrayBuffer is collected.
+    pub fn from_boxed(_guard: &'g Guard<'_>, data: Box<[u8]>) -> Result<Self> {
+        let len = byte_len(data.len())?;
+        let raw = Box::into_raw(data);
+        let state = Box::into_raw(Box::new(raw));
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        let code = unsafe {
+            sys::JsCreateExternalArrayBuffer(
+                raw as *mut u8 as *mut c_void,
+                len,
+                Some(free_boxed_bytes),
+                state as *mut c_void,
+                &mut out,
+            )
+        };
+        if code != sys::JsErrorCode::JsNoError {
+            // The engine never took ownership of either box.
+            unsafe { free_boxed_bytes(state as *mut c_void) };
+        }
+        ok_msg(code, "JsCreateExternalArrayBuffer failed")?;
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub fn from_vec(guard: &'g Guard<'_>, data: Vec<u8>) -> Result<Self> {
+        Self::from_boxed(guard, data.into_boxed_slice())
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    fn raw_storage(&self) -> Result<(*mut u8, u32)> {
+        let mut data: *mut u8 = std::ptr::null_mut();
+        let mut len: u32 = 0;
+        unsafe { ok(sys::JsGetArrayBufferStorage(self.v.raw, &mut data, &mut len))?; }
+        Ok((data, len))
+    }
+
+    fn storage(&self, guard: &Guard<'_>) -> Result<(*mut u8, u32)> {
+        let (data, len) = self.raw_storage()?;
+        check_attached(guard, ArrayBuffer { v: self.v }, data, len)?;
+        Ok((data, len))
+    }
+
+    pub fn len(&self, guard: &Guard<'_>) -> Result<usize> {
+        Ok(self.storage(guard)?.1 as usize)
+    }
+
+    // Takes the memory away from the buffer and every view of it, which are
+    // unusable from then on, in Rust and in script.
+    pub fn detach(&self, _guard: &Guard<'_>) -> Result<()> {
+        unsafe { ok_msg(sys::JsDetachArrayBuffer(self.v.raw), "JsDetachArrayBuffer failed") }
+    }
+
+    pub fn is_detached(&self, guard: &Guard<'_>) -> Result<bool> {
+        let (data, len) = self.raw_storage()?;
+        if !data.is_null() && len > 0 {
+            return Ok(false);
+        }
+        // JsRT has no query for it; `slice` throws on a detached buffer and
+        // nothing else.
+        let slice = Object::global(guard)?
+            .get(guard, "ArrayBuffer")?
+            .as_object(guard)
+            .ok_or_else(|| type_err("global ArrayBuffer is missing".to_string()))?
+            .get(guard, "prototype")?
+            .as_object(guard)
+            .ok_or_else(|| type_err("ArrayBuffer.prototype is missing".to_string()))?
+            .get(guard, "slice")?
+            .as_function(guard)
+            .ok_or_else(|| type_err("ArrayBuffer.prototype.slice is not a function".to_string()))?;
+        Ok(slice.call_with_this(guard, &self.v, &[]).is_err())
+    }
+
+    pub fn is_empty(&self, guard: &Guard<'_>) -> Result<bool> {
+        Ok(self.len(guard)? == 0)
+    }
+
+    /// # Safety
+    ///
+    /// Nothing may write to or detach the buffer while the slice is alive: no
+    /// mutable view of this memory and no script (see the note at the top of
+    /// this file).
+    pub unsafe fn as_bytes<'s>(&'s self, guard: &'s Guard<'_>) -> Result<&'s [u8]> {
+        let (data, len) = self.storage(guard)?;
+        Ok(bytes(data, len))
+    }
+
+    /// # Safety
+    ///
+    /// No other view of this memory may be alive while the slice is, and no
+    /// script may run (see the note at the top of this file).
+    pub unsafe fn as_bytes_mut<'s>(&'s mut self, guard: &'s Guard<'_>) -> Result<&'s mut [u8]> {
+        let (data, len) = self.storage(guard)?;
+        Ok(bytes_mut(data, len))
+    }
+
+    // Overwrite the whole buffer; `src` must have the same length.
+    pub fn copy_from_slice(&self, guard: &Guard<'_>, src: &[u8]) -> Result<()> {
+        let (data, len) = self.storage(guard)?;
+        unsafe { copy_into(data, len as usize, src) }
+    }
+
+    pub fn to_vec(&self, guard: &Guard<'_>) -> Result<Vec<u8>> {
+        let (data, len) = self.storage(guard)?;
+        Ok(unsafe { bytes(data, len) }.to_vec())
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
+
+unsafe extern "C" fn free_boxed_bytes(state: *mut c_void) {
+    let raw = Box::from_raw(state as *mut *mut [u8]);
+    drop(Box::from_raw(*raw));
+}
+
+// Rust element types matching a typed array kind.
+pub trait TypedArrayElement: Copy + 'static {
+    const KINDS: &'static [JsTypedArrayType];
+}
+
+macro_rules! typed_array_elements {
+    ($($t:ty => $($kind:ident)|+;)*) => {$(
+        impl TypedArrayElement for $t {
+            const KINDS: &'static [JsTypedArrayType] = &[$(JsTypedArrayType::$kind),+];
+        }
+    )*};
+}
+
+typed_array_elements! {
+    i8 => JsArrayTypeInt8;
+    u8 => JsArrayTypeUint8 | JsArrayTypeUint8Clamped;
+    i16 => JsArrayTypeInt16;
+    u16 => JsArrayTypeUint16;
+    i32 => JsArrayTypeInt32;
+    u32 => JsArrayTypeUint32;
+    f32 => JsArrayTypeFloat32;
+    f64 => JsArrayTypeFloat64;
+}
+
+pub struct TypedArray<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> TypedArray<'g> {
+    // A view of `len` elements of `buffer`, starting `byte_offset` bytes in.
+    pub fn new(
+        _guard: &'g Guard<'_>,
+        kind: JsTypedArrayType,
+        buffer: &ArrayBuffer<'_>,
+        byte_offset: u32,
+        len: u32,
+    ) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsCreateTypedArray(kind, buffer.v.raw, byte_offset, len, &mut out),
+                "JsCreateTypedArray failed",
+            )?;
+        }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    // A fresh, zero-filled typed array with its own buffer.
+    pub fn with_len(_guard: &'g Guard<'_>, kind: JsTypedArrayType, len: u32) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsCreateTypedArray(kind, sys::JS_INVALID_REFERENCE, 0, len, &mut out),
+                "JsCreateTypedArray failed",
+            )?;
+        }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    fn storage(&self, guard: &Guard<'_>) -> Result<(*mut u8, u32, JsTypedArrayType)> {
+        let mut data: *mut u8 = std::ptr::null_mut();
+        let mut len: u32 = 0;
+        let mut kind = JsTypedArrayType::JsArrayTypeUint8;
+        let mut element_size: i32 = 0;
+        unsafe {
+            ok(sys::JsGetTypedArrayStorage(self.v.raw, &mut data, &mut len, &mut kind, &mut element_size))?;
+        }
+        if data.is_null() || len == 0 {
+            check_attached(guard, self.buffer(guard)?, data, len)?;
+        }
+        Ok((data, len, kind))
+    }
+
+    pub fn kind(&self, guard: &Guard<'_>) -> Result<JsTypedArrayType> {
+        Ok(self.storage(guard)?.2)
+    }
+
+    pub fn buffer(&self, _guard: &Guard<'_>) -> Result<ArrayBuffer<'g>> {
+        let mut kind = JsTypedArrayType::JsArrayTypeUint8;
+        let mut buffer: sys::JsValueRef = std::ptr::null_mut();
+        let mut offset: u32 = 0;
+        let mut len: u32 = 0;
+        unsafe { ok(sys::JsGetTypedArrayInfo(self.v.raw, &mut kind, &mut buffer, &mut offset, &mut len))?; }
+        Ok(ArrayBuffer::from_value(Value::from_raw(buffer)))
+    }
+
+    // Length in bytes.
+    pub fn byte_len(&self, guard: &Guard<'_>) -> Result<usize> {
+        Ok(self.storage(guard)?.1 as usize)
+    }
+
+    /// # Safety
+    ///
+    /// As for `ArrayBuffer::as_bytes`.
+    pub unsafe fn as_bytes<'s>(&'s self, guard: &'s Guard<'_>) -> Result<&'s [u8]> {
+        let (data, len, _) = self.storage(guard)?;
+        Ok(bytes(data, len))
+    }
+
+    /// # Safety
+    ///
+    /// As for `ArrayBuffer::as_bytes_mut`.
+    pub unsafe fn as_bytes_mut<'s>(&'s mut self, guard: &'s Guard<'_>) -> Result<&'s mut [u8]> {
+        let (data, len, _) = self.storage(guard)?;
+        Ok(bytes_mut(data, len))
+    }
+
+    // The viewed bytes, whatever the element type.
+    pub fn to_byte_vec(&self, guard: &Guard<'_>) -> Result<Vec<u8>> {
+        let (data, len, _) = self.storage(guard)?;
+        Ok(unsafe { bytes(data, len) }.to_vec())
+    }
+
+    fn typed_storage<T: TypedArrayElement>(&self, guard: &Guard<'_>) -> Result<(*mut T, usize)> {
+        let (data, len, kind) = self.storage(guard)?;
+        if !T::KINDS.contains(&kind) {
+            return Err(type_err(format!(
+                "typed array is {:?}, not {}",
+                kind,
+                std::any::type_name::<T>()
+            )));
+        }
+        let data = data as *mut T;
+        if !data.is_null() && !data.is_aligned() {
+            return Err(type_err("typed array storage is not aligned".to_string()));
+        }
+        Ok((data, len as usize / std::mem::size_of::<T>()))
+    }
+
+    /// Elements as `T`; fails unless `T` matches the array kind.
+    ///
+    /// # Safety
+    ///
+    /// As for `ArrayBuffer::as_bytes`.
+    pub unsafe fn as_slice<'s, T: TypedArrayElement>(&'s self, guard: &'s Guard<'_>) -> Result<&'s [T]> {
+        let (data, len) = self.typed_storage::<T>(guard)?;
+        if data.is_null() || len == 0 {
+            return Ok(&[]);
+        }
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+
+    /// # Safety
+    ///
+    /// As for `ArrayBuffer::as_bytes_mut`.
+    pub unsafe fn as_mut_slice<'s, T: TypedArrayElement>(&'s mut self, guard: &'s Guard<'_>) -> Result<&'s mut [T]> {
+        let (data, len) = self.typed_storage::<T>(guard)?;
+        if data.is_null() || len == 0 {
+            return Ok(&mut []);
+        }
+        Ok(std::slice::from_raw_parts_mut(data, len))
+    }
+
+    // Overwrite every element; fails unless `T` matches the array kind and `src`
+    // has the same length.
+    pub fn copy_from_slice<T: TypedArrayElement>(&self, guard: &Guard<'_>, src: &[T]) -> Result<()> {
+        let (data, len) = self.typed_storage::<T>(guard)?;
+        unsafe { copy_into(data, len, src) }
+    }
+
+    pub fn to_vec<T: TypedArrayElement>(&self, guard: &Guard<'_>) -> Result<Vec<T>> {
+        let (data, len) = self.typed_storage::<T>(guard)?;
+        if data.is_null() || len == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(unsafe { std::slice::from_raw_parts(data, len) }.to_vec())
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
+
+pub struct DataView<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> DataView<'g> {
+    pub fn new(_guard: &'g Guard<'_>, buffer: &ArrayBuffer<'_>, byte_offset: u32, byte_len: u32) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsCreateDataView(buffer.v.raw, byte_offset, byte_len, &mut out),
+                "JsCreateDataView failed",
+            )?;
+        }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    fn storage(&self, guard: &Guard<'_>) -> Result<(*mut u8, u32)> {
+        let mut data: *mut u8 = std::ptr::null_mut();
+        let mut len: u32 = 0;
+        unsafe { ok(sys::JsGetDataViewStorage(self.v.raw, &mut data, &mut len))?; }
+        if data.is_null() || len == 0 {
+            check_attached(guard, self.buffer(guard)?, data, len)?;
+        }
+        Ok((data, len))
+    }
+
+    // JsRT has no call for it, so this reads the `buffer` property.
+    pub fn buffer(&self, guard: &Guard<'_>) -> Result<ArrayBuffer<'g>> {
+        Object::from_value(self.v)
+            .get(guard, "buffer")?
+            .as_array_buffer(guard)
+            .ok_or_else(|| type_err("DataView has no buffer".to_string()))
+    }
+
+    pub fn byte_len(&self, guard: &Guard<'_>) -> Result<usize> {
+        Ok(self.storage(guard)?.1 as usize)
+    }
+
+    /// # Safety
+    ///
+    /// As for `ArrayBuffer::as_bytes`.
+    pub unsafe fn as_bytes<'s>(&'s self, guard: &'s Guard<'_>) -> Result<&'s [u8]> {
+        let (data, len) = self.storage(guard)?;
+        Ok(bytes(data, len))
+    }
+
+    /// # Safety
+    ///
+    /// As for `ArrayBuffer::as_bytes_mut`.
+    pub unsafe fn as_bytes_mut<'s>(&'s mut self, guard: &'s Guard<'_>) -> Result<&'s mut [u8]> {
+        let (data, len) = self.storage(guard)?;
+        Ok(bytes_mut(data, len))
+    }
+
+    // Overwrite the whole buffer; `src` must have the same length.
+    pub fn copy_from_slice(&self, guard: &Guard<'_>, src: &[u8]) -> Result<()> {
+        let (data, len) = self.storage(guard)?;
+        unsafe { copy_into(data, len as usize, src) }
+    }
+
+    pub fn to_vec(&self, guard: &Guard<'_>) -> Result<Vec<u8>> {
+        let (data, len) = self.storage(guard)?;
+        Ok(unsafe { bytes(data, len) }.to_vec())
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
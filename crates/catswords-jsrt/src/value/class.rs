@@ -0,0 +1,325 @@
+use crate::error::{ok_msg, type_err, Result};
+use crate::guard::Guard;
+use crate::runtime::{Runtime, RuntimeInner};
+use crate::value::{CallInfo, Function, Object, PersistentValue, PropertyDescriptor, Value};
+use catswords_jsrt_sys as sys;
+use std::any::TypeId;
+use std::cell::{Ref, RefCell, RefMut};
+use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::rc;
+
+// External data of every instance created by a Class. The type id comes first
+// (repr(C)) so it can be checked before the payload type is known.
+#[repr(C)]
+struct Instance<T> {
+    type_id: TypeId,
+    // The object carrying this instance, as registered with the runtime.
+    object: usize,
+    runtime: rc::Weak<RuntimeInner>,
+    value: RefCell<T>,
+}
+
+unsafe extern "C" fn finalize_instance<T: 'static>(data: *mut c_void) {
+    if data.is_null() {
+        return;
+    }
+    let instance = Box::from_raw(data as *mut Instance<T>);
+    match Runtime::from_handle(&instance.runtime) {
+        // Like callback states, `T` is dropped after the collection: it may hold
+        // persistent values, and the engine refuses JsRelease in there.
+        Some(runtime) => {
+            runtime.unregister_instance(instance.object);
+            runtime.defer_drop(instance);
+        }
+        // Disposal: nothing is released any more and no one is left to report a
+        // panic to, but it must not unwind into the engine.
+        None => {
+            let _ = panic::catch_unwind(AssertUnwindSafe(move || drop(instance)));
+        }
+    }
+}
+
+fn instance<'g, T: 'static>(guard: &Guard<'_>, value: &Value<'g>) -> Result<&'g RefCell<T>> {
+    let foreign = || type_err(format!("receiver is not a {}", std::any::type_name::<T>()));
+    // Other external objects carry data of unknown layout; never read it.
+    if !guard.runtime().is_instance(value.raw) {
+        return Err(foreign());
+    }
+    let mut data: *mut c_void = std::ptr::null_mut();
+    if unsafe { sys::JsGetExternalData(value.raw, &mut data) } != sys::JsErrorCode::JsNoError || data.is_null() {
+        return Err(foreign());
+    }
+    let type_id = unsafe { *(data as *const TypeId) };
+    if type_id != TypeId::of::<T>() {
+        return Err(foreign());
+    }
+    // The instance lives until the object is collected, which the caller roots against.
+    Ok(unsafe { &(*(data as *const Instance<T>)).value })
+}
+
+// A shared borrow of the Rust value behind a class instance. It roots the
+// object, so the value outlives any collection script triggers meanwhile.
+pub struct InstanceRef<'g, T: 'static> {
+    // Released before the root.
+    value: Ref<'g, T>,
+    _root: PersistentValue,
+}
+
+impl<T> Deref for InstanceRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// An exclusive borrow of the Rust value behind a class instance; see `InstanceRef`.
+pub struct InstanceMut<'g, T: 'static> {
+    value: RefMut<'g, T>,
+    _root: PersistentValue,
+}
+
+impl<T> Deref for InstanceMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for InstanceMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'g> Value<'g> {
+    // Borrow the Rust value behind an instance of a `Class<T>`. Fails with a
+    // TypeError for any other value, or if it is already mutably borrowed.
+    pub fn downcast_ref<T: 'static>(&self, guard: &Guard<'_>) -> Result<InstanceRef<'g, T>> {
+        let cell = instance::<T>(guard, self)?;
+        let root = PersistentValue::new(guard, self)?;
+        let value = cell
+            .try_borrow()
+            .map_err(|_| type_err(format!("{} is already borrowed mutably", std::any::type_name::<T>())))?;
+        Ok(InstanceRef { value, _root: root })
+    }
+
+    pub fn downcast_mut<T: 'static>(&self, guard: &Guard<'_>) -> Result<InstanceMut<'g, T>> {
+        let cell = instance::<T>(guard, self)?;
+        let root = PersistentValue::new(guard, self)?;
+        let value = cell
+            .try_borrow_mut()
+            .map_err(|_| type_err(format!("{} is already borrowed", std::any::type_name::<T>())))?;
+        Ok(InstanceMut { value, _root: root })
+    }
+}
+
+impl<'g> CallInfo<'g> {
+    pub fn this_ref<T: 'static>(&self, guard: &Guard<'_>) -> Result<InstanceRef<'g, T>> {
+        self.this.downcast_ref(guard)
+    }
+
+    pub fn this_mut<T: 'static>(&self, guard: &Guard<'_>) -> Result<InstanceMut<'g, T>> {
+        self.this.downcast_mut(guard)
+    }
+}
+
+// Builds a JavaScript class whose instances wrap a Rust `T`:
+//
+//     let point = Class::<Point>::new(&guard, "Point", |guard, info| Ok(Point::new(..)))?
+//         .method("norm", |guard, p, _info| p.norm().into_js(guard))?
+//         .accessor("x", |guard, p| p.x.into_js(guard), |_, p, v| { p.x = v; Ok(()) })?
+//         .build()?;
+//     guard.context().set_global("Point", point.as_value())?;
+//
+// Methods and accessors go on the prototype and are non-enumerable, like
+// class members written in script. Calling them on a foreign receiver throws a
+// TypeError. The instance is dropped when its object is garbage collected.
+pub struct Class<'g, T> {
+    guard: &'g Guard<'g>,
+    constructor: Function<'g>,
+    prototype: Object<'g>,
+    _type: std::marker::PhantomData<fn() -> T>,
+}
+
+fn member<'a>(value: Value<'a>) -> PropertyDescriptor<'a> {
+    PropertyDescriptor::new().value(value).writable(true).enumerable(false).configurable(true)
+}
+
+impl<'g, T: Send + 'static> Class<'g, T> {
+    pub fn new<F>(guard: &'g Guard<'g>, name: &str, constructor: F) -> Result<Self>
+    where
+        F: for<'a> Fn(&'a Guard<'_>, &CallInfo<'a>) -> Result<T> + Send + 'static,
+    {
+        let class_name = name.to_string();
+        let constructor = Function::new(guard, Box::new(move |guard, info| {
+            if !info.is_construct_call {
+                return Err(type_err(format!("class constructor {} cannot be invoked without 'new'", class_name)));
+            }
+            let value = constructor(guard, &info)?;
+
+            // `new.target.prototype`, so subclasses declared in script keep their own prototype.
+            let prototype = match info.new_target.as_object(guard) {
+                Some(target) => target.get(guard, "prototype")?,
+                None => Value::null(guard)?,
+            };
+
+            let data = Box::into_raw(Box::new(Instance {
+                type_id: TypeId::of::<T>(),
+                object: 0,
+                runtime: guard.runtime().handle(),
+                value: RefCell::new(value),
+            }));
+            let mut out: sys::JsValueRef = std::ptr::null_mut();
+            let code = unsafe {
+                sys::JsCreateExternalObjectWithPrototype(
+                    data as *mut c_void,
+                    Some(finalize_instance::<T>),
+                    prototype.raw,
+                    &mut out,
+                )
+            };
+            if code != sys::JsErrorCode::JsNoError {
+                drop(unsafe { Box::from_raw(data) });
+            }
+            ok_msg(code, "JsCreateExternalObjectWithPrototype failed")?;
+            unsafe { (*data).object = out as usize };
+            guard.runtime().register_instance(out);
+            Ok(Value::from_raw(out))
+        }));
+
+        let prototype = Object::new(guard)?;
+        let ctor = constructor.as_value().as_object(guard).ok_or_else(|| type_err("constructor is not an object".to_string()))?;
+        ctor.define_property(guard, "prototype", &PropertyDescriptor::new().value(*prototype.as_value()))?;
+        prototype.define_property(guard, "constructor", &member(*constructor.as_value()))?;
+
+        Ok(Self { guard, constructor, prototype, _type: std::marker::PhantomData })
+    }
+
+    // Method with shared access to the instance; may re-enter script freely.
+    pub fn method<F>(self, name: &str, f: F) -> Result<Self>
+    where
+        F: for<'a> Fn(&'a Guard<'_>, &T, CallInfo<'a>) -> Result<Value<'a>> + Send + 'static,
+    {
+        let method = Function::new(self.guard, Box::new(move |guard, info| {
+            let this = info.this_ref::<T>(guard)?;
+            f(guard, &this, info)
+        }));
+        self.prototype.define_property(self.guard, name, &member(method.into()))?;
+        Ok(self)
+    }
+
+    // Method with exclusive access; re-entering the same instance from script
+    // while it runs throws a TypeError.
+    pub fn method_mut<F>(self, name: &str, f: F) -> Result<Self>
+    where
+        F: for<'a> Fn(&'a Guard<'_>, &mut T, CallInfo<'a>) -> Result<Value<'a>> + Send + 'static,
+    {
+        let method = Function::new(self.guard, Box::new(move |guard, info| {
+            let mut this = info.this_mut::<T>(guard)?;
+            f(guard, &mut this, info)
+        }));
+        self.prototype.define_property(self.guard, name, &member(method.into()))?;
+        Ok(self)
+    }
+
+    // Read-only property.
+    pub fn getter<G>(self, name: &str, get: G) -> Result<Self>
+    where
+        G: for<'a> Fn(&'a Guard<'_>, &T) -> Result<Value<'a>> + Send + 'static,
+    {
+        let desc = PropertyDescriptor::new()
+            .getter(Self::getter_fn(self.guard, get))
+            .enumerable(false)
+            .configurable(true);
+        self.prototype.define_property(self.guard, name, &desc)?;
+        Ok(self)
+    }
+
+    pub fn accessor<G, S>(self, name: &str, get: G, set: S) -> Result<Self>
+    where
+        G: for<'a> Fn(&'a Guard<'_>, &T) -> Result<Value<'a>> + Send + 'static,
+        S: for<'a> Fn(&'a Guard<'_>, &mut T, Value<'a>) -> Result<()> + Send + 'static,
+    {
+        let setter = Function::new(self.guard, Box::new(move |guard, info| {
+            let value = match info.arguments.first() {
+                Some(v) => *v,
+                None => Value::undefined(guard)?,
+            };
+            let mut this = info.this_mut::<T>(guard)?;
+            set(guard, &mut this, value)?;
+            Value::undefined(guard)
+        }));
+        let desc = PropertyDescriptor::new()
+            .getter(Self::getter_fn(self.guard, get))
+            .setter(setter)
+            .enumerable(false)
+            .configurable(true);
+        self.prototype.define_property(self.guard, name, &desc)?;
+        Ok(self)
+    }
+
+    fn getter_fn<G>(guard: &'g Guard<'g>, get: G) -> Function<'g>
+    where
+        G: for<'a> Fn(&'a Guard<'_>, &T) -> Result<Value<'a>> + Send + 'static,
+    {
+        Function::new(guard, Box::new(move |guard, info| {
+            let this = info.this_ref::<T>(guard)?;
+            get(guard, &this)
+        }))
+    }
+
+    // Static members live on the constructor itself.
+    pub fn static_method<F>(self, name: &str, f: F) -> Result<Self>
+    where
+        F: for<'a> Fn(&'a Guard<'_>, CallInfo<'a>) -> Result<Value<'a>> + Send + 'static,
+    {
+        let method = Function::new(self.guard, Box::new(f));
+        self.constructor_object()?.define_property(self.guard, name, &member(method.into()))?;
+        Ok(self)
+    }
+
+    pub fn static_value(self, name: &str, value: &Value<'_>) -> Result<Self> {
+        let desc = PropertyDescriptor::new().value(Value::from_raw(value.raw)).enumerable(false);
+        self.constructor_object()?.define_property(self.guard, name, &desc)?;
+        Ok(self)
+    }
+
+    fn constructor_object(&self) -> Result<Object<'g>> {
+        self.constructor
+            .as_value()
+            .as_object(self.guard)
+            .ok_or_else(|| type_err("constructor is not an object".to_string()))
+    }
+
+    pub fn prototype(&self) -> &Object<'g> {
+        &self.prototype
+    }
+
+    // The constructor function, ready to be stored on the global object.
+    pub fn build(self) -> Result<Function<'g>> {
+        Ok(self.constructor)
+    }
+}
+
+// Implemented by `#[js_class]`: the name the constructor is registered under.
+pub trait JsClass: Send + Sized + 'static {
+    const NAME: &'static str;
+}
+
+// Implemented by `#[js_methods]` for the impl block of a `#[js_class]` type.
+pub trait JsMethods: JsClass {
+    fn construct<'a>(guard: &'a Guard<'_>, info: &CallInfo<'a>) -> Result<Self>;
+
+    fn members<'g>(class: Class<'g, Self>) -> Result<Class<'g, Self>>;
+}
+
+impl<'g, T: JsMethods> Class<'g, T> {
+    // The constructor of a class declared with `#[js_class]` / `#[js_methods]`.
+    pub fn define(guard: &'g Guard<'g>) -> Result<Function<'g>> {
+        T::members(Class::new(guard, T::NAME, T::construct)?)?.build()
+    }
+}
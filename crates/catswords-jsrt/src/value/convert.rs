@@ -0,0 +1,323 @@
+use crate::error::{type_err, Error, Result};
+use crate::guard::Guard;
+use crate::value::number::checked_integer;
+use crate::value::{
+    Array, ArrayBuffer, Boolean, DataView, Function, JsString, Number, Object, Symbol, TypedArray, Value,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+// Rust -> JavaScript. `'g` is the guard borrow the produced value lives for.
+pub trait IntoJs<'g> {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>>;
+}
+
+// JavaScript -> Rust. Mismatches are reported as `Error::Type`.
+pub trait FromJs<'g>: Sized {
+    // True for types that accept undefined, so a missing argument is not an arity error.
+    const OPTIONAL: bool = false;
+
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self>;
+}
+
+fn mismatch(guard: &Guard<'_>, expected: &str, value: &Value<'_>) -> Error {
+    type_err(format!("expected {}, got {}", expected, value.type_name(guard)))
+}
+
+// Prefix nested conversion errors with where they happened ("element 2: ...").
+#[doc(hidden)]
+pub fn within(e: Error, at: impl std::fmt::Display) -> Error {
+    match e {
+        Error::Type(msg) => Error::Type(Cow::Owned(format!("{}: {}", at, msg))),
+        other => other,
+    }
+}
+
+impl<'g> IntoJs<'g> for Value<'g> {
+    fn into_js(self, _guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Ok(self)
+    }
+}
+
+impl<'g> FromJs<'g> for Value<'g> {
+    fn from_js(_guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        Ok(*value)
+    }
+}
+
+impl<'g> IntoJs<'g> for () {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Value::undefined(guard)
+    }
+}
+
+impl<'g> IntoJs<'g> for bool {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Ok(Boolean::new(guard, self)?.into())
+    }
+}
+
+impl<'g> FromJs<'g> for bool {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        match value.as_boolean(guard) {
+            Some(b) => b.value(guard),
+            None => Err(mismatch(guard, "boolean", value)),
+        }
+    }
+}
+
+fn number_from_js(guard: &Guard<'_>, value: &Value<'_>) -> Result<f64> {
+    match value.as_number(guard) {
+        Some(n) => n.value(guard),
+        None => Err(mismatch(guard, "number", value)),
+    }
+}
+
+macro_rules! small_int_conversions {
+    ($($t:ty),*) => {$(
+        impl<'g> IntoJs<'g> for $t {
+            fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+                Ok(Number::new(guard, self as i32).into())
+            }
+        }
+
+        impl<'g> FromJs<'g> for $t {
+            fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+                let n = number_from_js(guard, value)?;
+                Ok(checked_integer(n, <$t>::MIN as f64, <$t>::MAX as f64 + 1.0, stringify!($t))? as $t)
+            }
+        }
+    )*};
+}
+
+small_int_conversions!(i8, i16, i32, u8, u16);
+
+// Wider integers go through f64; magnitudes above 2^53 lose precision on the way in,
+// exactly as they would in JavaScript.
+macro_rules! wide_int_conversions {
+    ($($t:ty),*) => {$(
+        impl<'g> IntoJs<'g> for $t {
+            fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+                Ok(Number::from_f64(guard, self as f64).into())
+            }
+        }
+
+        impl<'g> FromJs<'g> for $t {
+            fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+                let n = number_from_js(guard, value)?;
+                // MAX as f64 is not the exclusive bound everywhere (it is exact for a
+                // 32-bit usize), but 2^BITS, or 2^(BITS - 1) when signed, always is.
+                let signed = <$t>::MIN != 0;
+                let max = 2f64.powi((<$t>::BITS - signed as u32) as i32);
+                Ok(checked_integer(n, <$t>::MIN as f64, max, stringify!($t))? as $t)
+            }
+        }
+    )*};
+}
+
+wide_int_conversions!(i64, u64, isize, usize, i128, u128);
+
+impl<'g> IntoJs<'g> for u32 {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Ok(Number::from_f64(guard, self as f64).into())
+    }
+}
+
+impl<'g> FromJs<'g> for u32 {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        let n = number_from_js(guard, value)?;
+        Ok(checked_integer(n, 0.0, u32::MAX as f64 + 1.0, "u32")? as u32)
+    }
+}
+
+impl<'g> IntoJs<'g> for f64 {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Ok(Number::from_f64(guard, self).into())
+    }
+}
+
+impl<'g> FromJs<'g> for f64 {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        number_from_js(guard, value)
+    }
+}
+
+impl<'g> IntoJs<'g> for f32 {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Ok(Number::from_f64(guard, self as f64).into())
+    }
+}
+
+impl<'g> FromJs<'g> for f32 {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        Ok(number_from_js(guard, value)? as f32)
+    }
+}
+
+impl<'g> IntoJs<'g> for &str {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Value::string_utf8(guard, self)
+    }
+}
+
+impl<'g> IntoJs<'g> for String {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        Value::string_utf8(guard, &self)
+    }
+}
+
+impl<'g> FromJs<'g> for String {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        match value.as_string(guard) {
+            Some(s) => s.to_rust_string(guard),
+            None => Err(mismatch(guard, "string", value)),
+        }
+    }
+}
+
+// None becomes null; both null and undefined read back as None.
+impl<'g, T: IntoJs<'g>> IntoJs<'g> for Option<T> {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        match self {
+            Some(v) => v.into_js(guard),
+            None => Value::null(guard),
+        }
+    }
+}
+
+impl<'g, T: FromJs<'g>> FromJs<'g> for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        if value.is_undefined(guard) || value.is_null(guard) {
+            Ok(None)
+        } else {
+            T::from_js(guard, value).map(Some)
+        }
+    }
+}
+
+impl<'g, T: IntoJs<'g>> IntoJs<'g> for Vec<T> {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        let array = Array::new(guard, self.len() as u32)?;
+        for (i, item) in self.into_iter().enumerate() {
+            let v = item.into_js(guard)?;
+            array.set(guard, i as u32, &v)?;
+        }
+        Ok(array.into())
+    }
+}
+
+impl<'g, T: FromJs<'g>> FromJs<'g> for Vec<T> {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        let array = value.as_array(guard).ok_or_else(|| mismatch(guard, "array", value))?;
+        let len = array.len(guard)?;
+        let mut out = Vec::with_capacity(len as usize);
+        for i in 0..len {
+            let item = array.get(guard, i)?;
+            out.push(T::from_js(guard, &item).map_err(|e| within(e, format_args!("element {}", i)))?);
+        }
+        Ok(out)
+    }
+}
+
+impl<'g, T: IntoJs<'g>, S: BuildHasher> IntoJs<'g> for HashMap<String, T, S> {
+    fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        let object = Object::new(guard)?;
+        for (key, item) in self {
+            let v = item.into_js(guard)?;
+            object.set(guard, &key, &v)?;
+        }
+        Ok(object.into())
+    }
+}
+
+impl<'g, T: FromJs<'g>, S: BuildHasher + Default> FromJs<'g> for HashMap<String, T, S> {
+    fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+        let object = value.as_object(guard).ok_or_else(|| mismatch(guard, "object", value))?;
+        let names = object.get_own_property_names(guard)?;
+        let len = names.len(guard)?;
+        let mut out = HashMap::with_capacity_and_hasher(len as usize, S::default());
+        for i in 0..len {
+            let key = String::from_js(guard, &names.get(guard, i)?)?;
+            let item = object.get(guard, &key)?;
+            let v = T::from_js(guard, &item).map_err(|e| within(e, format_args!("property {:?}", key)))?;
+            out.insert(key, v);
+        }
+        Ok(out)
+    }
+}
+
+// Tuples map to fixed-length arrays.
+macro_rules! tuple_conversions {
+    ($len:expr; $($name:ident $idx:tt),+) => {
+        impl<'g, $($name: IntoJs<'g>),+> IntoJs<'g> for ($($name,)+) {
+            fn into_js(self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+                let array = Array::new(guard, $len)?;
+                $(
+                    let v = self.$idx.into_js(guard)?;
+                    array.set(guard, $idx, &v)?;
+                )+
+                Ok(array.into())
+            }
+        }
+
+        impl<'g, $($name: FromJs<'g>),+> FromJs<'g> for ($($name,)+) {
+            fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+                let array = value.as_array(guard).ok_or_else(|| mismatch(guard, "array", value))?;
+                let len = array.len(guard)?;
+                if len != $len {
+                    return Err(type_err(format!("expected array of length {}, got length {}", $len, len)));
+                }
+                Ok(($(
+                    $name::from_js(guard, &array.get(guard, $idx)?)
+                        .map_err(|e| within(e, format_args!("element {}", $idx)))?,
+                )+))
+            }
+        }
+    };
+}
+
+tuple_conversions!(1; A 0);
+tuple_conversions!(2; A 0, B 1);
+tuple_conversions!(3; A 0, B 1, C 2);
+tuple_conversions!(4; A 0, B 1, C 2, D 3);
+tuple_conversions!(5; A 0, B 1, C 2, D 3, E 4);
+tuple_conversions!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_conversions!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_conversions!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple_conversions!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple_conversions!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple_conversions!(11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple_conversions!(12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+// Typed wrappers convert to their value and back through the checked downcasts.
+macro_rules! wrapper_conversions {
+    ($($t:ident => $as:ident, $expected:expr;)*) => {$(
+        impl<'g> IntoJs<'g> for $t<'g> {
+            fn into_js(self, _guard: &'g Guard<'_>) -> Result<Value<'g>> {
+                Ok(self.into())
+            }
+        }
+
+        impl<'g> FromJs<'g> for $t<'g> {
+            fn from_js(guard: &Guard<'_>, value: &Value<'g>) -> Result<Self> {
+                value.$as(guard).ok_or_else(|| mismatch(guard, $expected, value))
+            }
+        }
+    )*};
+}
+
+wrapper_conversions! {
+    Object => as_object, "object";
+    Array => as_array, "array";
+    JsString => as_string, "string";
+    Boolean => as_boolean, "boolean";
+    Symbol => as_symbol, "symbol";
+    Number => as_number, "number";
+    Function => as_function, "function";
+    ArrayBuffer => as_array_buffer, "ArrayBuffer";
+    TypedArray => as_typed_array, "TypedArray";
+    DataView => as_data_view, "DataView";
+}
@@ -1,31 +1,46 @@
-use crate::error::{ok, Result};
+use crate::error::{ok, Error, Result};
 use crate::guard::Guard;
-use crate::runtime::Runtime;
-use crate::value::Value;
+use crate::runtime::{PanicPolicy, Runtime, RuntimeInner};
+use crate::value::{NativeFn, Value};
 use catswords_jsrt_sys as sys;
+use std::any::Any;
 use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc;
 
-pub struct CallInfo {
-    pub arguments: Vec<Value>,
+pub struct CallInfo<'g> {
+    pub arguments: Vec<Value<'g>>,
+    // `this` as seen by script; undefined for plain calls in strict code.
+    pub this: Value<'g>,
+    // The function object being invoked.
+    pub callee: Value<'g>,
+    // True when invoked with `new`; `new_target` is then the constructor, otherwise undefined.
+    pub is_construct_call: bool,
+    pub new_target: Value<'g>,
 }
 
-type Callback = dyn Fn(&Guard<'_>, CallInfo) -> Result<Value> + Send + Sync + 'static;
+// Values handed to the callback live as long as its guard borrow, so none of them
+// can be smuggled out of the call.
+// Callbacks only ever run on the runtime's thread, but must be Send so the runtime
+// can move as a SendableRuntime.
+type Callback = dyn for<'g> Fn(&'g Guard<'_>, CallInfo<'g>) -> Result<Value<'g>> + Send + 'static;
 
-// NEW: callback_state holds both runtime and callback.
+// callback_state holds both runtime and callback. The runtime is reached through
+// its heap-stable inner state, so it stays valid when the `Runtime` value moves.
 struct CallbackState {
-    runtime: *const Runtime,
+    runtime: rc::Weak<RuntimeInner>,
     cb: Box<Callback>,
 }
 
-pub struct Function {
-    v: Value,
+pub struct Function<'g> {
+    v: Value<'g>,
 }
 
-impl Function {
-    pub fn new(guard: &Guard<'_>, cb: Box<Callback>) -> Self {
+impl<'g> Function<'g> {
+    pub fn new(guard: &'g Guard<'_>, cb: Box<Callback>) -> Self {
         // Allocate callback state (thin pointer)
         let state = Box::new(CallbackState {
-            runtime: guard.runtime() as *const Runtime,
+            runtime: guard.runtime().handle(),
             cb,
         });
         let state_ptr = Box::into_raw(state) as *mut c_void;
@@ -35,94 +50,210 @@ impl Function {
 
         let mut func: sys::JsValueRef = std::ptr::null_mut();
         unsafe {
-            let _ = ok(sys::JsCreateFunction(Some(native_trampoline), state_ptr, &mut func));
+            let _ = ok(sys::JsCreateEnhancedFunction(
+                Some(native_trampoline),
+                sys::JS_INVALID_REFERENCE,
+                state_ptr,
+                &mut func,
+            ));
+            // Free the state as soon as the function is collected, so short-lived
+            // functions (a JSON reviver, say) do not pile up until disposal.
+            if !func.is_null() {
+                let _ = sys::JsSetObjectBeforeCollectCallback(func, state_ptr, Some(collect_callback_state));
+            }
         }
 
-        Self { v: Value { raw: func } }
+        Self { v: Value::from_raw(func) }
     }
 
-    pub fn call(&self, _guard: &Guard<'_>, args: &[&Value]) -> Result<Value> {
-        // ChakraCore requires argv[0] = thisArg. We'll use the function itself as thisArg.
-        let mut argv: Vec<sys::JsValueRef> = Vec::with_capacity(args.len() + 1);
-        argv.push(self.v.raw);
-        for a in args {
-            argv.push(a.raw);
-        }
+    // Build a function from a plain closure such as `|a: i32, b: f64| -> Result<f64>`.
+    // Arguments are converted with FromJs (Option<T> may be omitted, Rest<T> takes
+    // the remainder), extra arguments are ignored and the result goes through IntoJs.
+    pub fn wrap<Args, F: NativeFn<Args>>(guard: &'g Guard<'_>, f: F) -> Self {
+        Self::new(guard, Box::new(move |guard, info| f.invoke(guard, &info.arguments)))
+    }
 
+    // Calls with the function itself as `this`, as this crate always has. Use
+    // `call_plain` for `f(...args)` semantics.
+    pub fn call(&self, guard: &Guard<'_>, args: &[&Value<'_>]) -> Result<Value<'g>> {
+        self.call_with_this(guard, &self.v, args)
+    }
+
+    // Plain call, as `f(...args)`: `this` is undefined.
+    pub fn call_plain(&self, guard: &Guard<'_>, args: &[&Value<'_>]) -> Result<Value<'g>> {
+        self.call_with_this(guard, &Value::undefined(guard)?, args)
+    }
+
+    // Method call, as `this.f(...args)`.
+    pub fn call_with_this(&self, _guard: &Guard<'_>, this: &Value<'_>, args: &[&Value<'_>]) -> Result<Value<'g>> {
+        // ChakraCore requires argv[0] = thisArg.
+        let mut argv = with_this(this.raw, args);
         let mut out: sys::JsValueRef = std::ptr::null_mut();
-        unsafe { ok(sys::JsCallFunction(self.v.raw, argv.as_ptr(), argv.len() as u16, &mut out))?; }
-        Ok(Value { raw: out })
+        unsafe { ok(sys::JsCallFunction(self.v.raw, argv.as_mut_ptr(), argv.len() as u16, &mut out))?; }
+        Ok(Value::from_raw(out))
     }
 
-    pub fn into(self) -> Value {
+    // Constructor call, as `new f(...args)`.
+    pub fn construct(&self, guard: &Guard<'_>, args: &[&Value<'_>]) -> Result<Value<'g>> {
+        // argv[0] is ignored for construct calls; the engine creates `this` itself.
+        let mut argv = with_this(Value::undefined(guard)?.raw, args);
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsConstructObject(self.v.raw, argv.as_mut_ptr(), argv.len() as u16, &mut out))?; }
+        Ok(Value::from_raw(out))
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
         self.v
     }
 }
 
-impl Drop for Function {
+fn with_this(this: sys::JsValueRef, args: &[&Value<'_>]) -> Vec<sys::JsValueRef> {
+    let mut argv: Vec<sys::JsValueRef> = Vec::with_capacity(args.len() + 1);
+    argv.push(this);
+    for a in args {
+        argv.push(a.raw);
+    }
+    argv
+}
+
+impl Drop for Function<'_> {
     fn drop(&mut self) {
-        // Do NOT free callback_state here.
-        // Runtime will free all callback states after JsDisposeRuntime.
+        // Do NOT free callback_state here; script may still hold the function.
+        // It is freed when the function is collected, or after JsDisposeRuntime.
+    }
+}
+
+unsafe extern "C" fn collect_callback_state(_function: sys::JsRef, callback_state: *mut c_void) {
+    let st = &*(callback_state as *const CallbackState);
+    // Once disposal has begun the runtime frees every remaining state itself.
+    // Otherwise the closure is dropped later: whatever it captured may need
+    // JsRelease, which the engine refuses during a collection.
+    if let Some(runtime) = Runtime::from_handle(&st.runtime) {
+        if runtime.unregister_callback_state(callback_state) {
+            runtime.defer_drop(take_callback_state(callback_state));
+        }
     }
 }
 
 unsafe extern "C" fn native_trampoline(
-    _callee: sys::JsValueRef,
-    _is_construct_call: bool,
-    arguments: *const sys::JsValueRef,
+    callee: sys::JsValueRef,
+    arguments: *mut sys::JsValueRef,
     argument_count: u16,
+    call_info: *mut sys::JsNativeFunctionInfo,
     callback_state: *mut c_void,
 ) -> sys::JsValueRef {
     // Cast back to CallbackState
     let st = &*(callback_state as *const CallbackState);
     let cb: &Callback = &*st.cb;
 
-    // Current context (assume host already set current context)
-    let mut current: sys::JsContextRef = std::ptr::null_mut();
-    let _ = sys::JsGetCurrentContext(&mut current);
-
-    // Build Guard that includes runtime reference
-    let guard = Guard {
-        prev: current,
-        current,
-        runtime: &*st.runtime,
-        _marker: std::marker::PhantomData,
+    // Only fails while the runtime is being disposed, when no script can run.
+    let Some(runtime) = Runtime::from_handle(&st.runtime) else {
+        return sys::JS_INVALID_REFERENCE;
     };
 
+    // Build Guard that includes runtime reference (the engine already set the current context)
+    let guard = Guard::in_callback(&runtime);
+
     // Copy args
-    let mut argv: Vec<Value> = Vec::with_capacity(argument_count as usize);
+    let mut argv: Vec<Value<'_>> = Vec::with_capacity(argument_count as usize);
     if !arguments.is_null() {
         let slice = std::slice::from_raw_parts(arguments, argument_count as usize);
         for &a in slice {
-            argv.push(Value { raw: a });
+            argv.push(Value::from_raw(a));
         }
     }
 
     // ChakraCore passes thisArg at argv[0]. The user's closure expects only user args.
     let user_args = if argv.len() >= 2 { argv[1..].to_vec() } else { Vec::new() };
-    let info = CallInfo { arguments: user_args };
+    let native = &*call_info;
+    let info = CallInfo {
+        arguments: user_args,
+        this: Value::from_raw(native.thisArg),
+        callee: Value::from_raw(callee),
+        is_construct_call: native.isConstructCall,
+        new_target: Value::from_raw(native.newTargetArg),
+    };
+
+    // Never let a panic unwind across the extern "C" boundary.
+    let result = match panic::catch_unwind(AssertUnwindSafe(|| cb(&guard, info))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let msg = report_panic(guard.runtime(), "native callback", &*payload);
+            return throw(&guard, Value::error_from_message(&guard, &msg));
+        }
+    };
 
-    match cb(&guard, info) {
+    match result {
         Ok(v) => v.raw,
+        Err(Error::JsException(e)) => match e.value(&guard) {
+            // Rethrow the original value so script-side catch blocks see it unchanged.
+            Ok(thrown) => {
+                let _ = sys::JsSetException(thrown.raw);
+                thrown.raw
+            }
+            Err(_) => throw(&guard, Value::error_from_message(&guard, &e.message)),
+        },
         Err(e) => {
-            let msg = format!("{}", e);
+            let created = match &e {
+                Error::Type(msg) => Value::type_error_from_message(&guard, msg),
+                _ => Value::error_from_message(&guard, &format!("{}", e)),
+            };
+            throw(&guard, created)
+        }
+    }
+}
 
-            if let Ok(js_err) = Value::error_from_message(&guard, &msg) {
-                let _ = sys::JsSetException(js_err.raw);
-                return js_err.raw;
-            }
+unsafe fn throw(_guard: &Guard<'_>, created: Result<Value<'_>>) -> sys::JsValueRef {
+    if let Ok(js_err) = created {
+        let _ = sys::JsSetException(js_err.raw);
+        return js_err.raw;
+    }
+
+    // Fallback to undefined if error creation fails
+    let mut undef = std::ptr::null_mut();
+    let _ = sys::JsGetUndefinedValue(&mut undef);
+    let _ = sys::JsSetException(undef);
+    undef
+}
 
-            // Fallback to undefined if error creation fails
-            let mut undef = std::ptr::null_mut();
-            let _ = sys::JsGetUndefinedValue(&mut undef);
-            let _ = sys::JsSetException(undef);
-            undef
+// Apply the runtime's panic policy to a panic caught in `what` and return the
+// message to throw in its place.
+pub(crate) fn report_panic(runtime: &Runtime, what: &str, payload: &(dyn Any + Send)) -> String {
+    apply_panic_policy(runtime.panic_policy(), what, payload)
+}
+
+pub(crate) fn apply_panic_policy(policy: PanicPolicy, what: &str, payload: &(dyn Any + Send)) -> String {
+    let msg = format!("{} panicked: {}", what, panic_message(payload));
+    match policy {
+        PanicPolicy::Throw => {}
+        PanicPolicy::LogAndThrow => eprintln!("catswords-jsrt: {}", msg),
+        PanicPolicy::Abort => {
+            eprintln!("catswords-jsrt: {}", msg);
+            std::process::abort();
         }
     }
+    msg
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "<non-string panic payload>"
+    }
 }
 
-pub(crate) unsafe fn free_callback_state(p: *mut c_void) {
-    // p was created from Box::into_raw(Box<CallbackState>) as *mut c_void
-    let _state: Box<CallbackState> = Box::from_raw(p as *mut CallbackState);
-    // drop happens here
+// `p` must come from Box::into_raw(Box<CallbackState>) in `Function::new`.
+pub(crate) unsafe fn take_callback_state(p: *mut c_void) -> Box<dyn Any> {
+    Box::from_raw(p as *mut CallbackState)
 }
@@ -0,0 +1,108 @@
+use crate::error::{ok, type_err, Result};
+use crate::guard::Guard;
+use crate::value::{Function, Object, PropertyId, Value};
+use catswords_jsrt_sys as sys;
+
+// Walks any JavaScript iterable (arrays, strings, Map, Set, generators, ...)
+// through its `Symbol.iterator` protocol. Yields `Err` once if a step throws,
+// then stops. Dropped before the end, it closes the iterator through
+// `return()` like a `break` out of `for...of` would.
+pub struct JsIterator<'g, 'rt> {
+    guard: &'g Guard<'rt>,
+    iterator: Value<'g>,
+    next: Function<'g>,
+    done: bool,
+}
+
+impl<'g, 'rt> JsIterator<'g, 'rt> {
+    pub fn new(guard: &'g Guard<'rt>, iterable: &Value<'_>) -> Result<Self> {
+        let symbol_ctor = Object::global(guard)?.get(guard, "Symbol")?;
+        let symbol_ctor = symbol_ctor
+            .as_object(guard)
+            .ok_or_else(|| type_err("global Symbol is missing".to_string()))?;
+        let iterator_symbol = symbol_ctor
+            .get(guard, "iterator")?
+            .as_symbol(guard)
+            .ok_or_else(|| type_err("Symbol.iterator is not a symbol".to_string()))?;
+        let key = PropertyId::from_symbol(guard, &iterator_symbol)?;
+
+        let object = as_object(guard, iterable)?;
+        let factory = object.get(guard, &key)?.as_function(guard).ok_or_else(|| {
+            type_err(format!("{} is not iterable", iterable.type_name(guard)))
+        })?;
+        let iterator = factory.call_with_this(guard, iterable, &[])?;
+        let next = as_object(guard, &iterator)?
+            .get(guard, "next")?
+            .as_function(guard)
+            .ok_or_else(|| type_err("iterator has no next() method".to_string()))?;
+
+        Ok(Self { guard, iterator, next, done: false })
+    }
+
+    fn step(&mut self) -> Result<Option<Value<'g>>> {
+        let guard = self.guard;
+        let result = self.next.call_with_this(guard, &self.iterator, &[])?;
+        let result = as_object(guard, &result)?;
+
+        let done = result.get(guard, "done")?;
+        let mut done_bool: sys::JsValueRef = std::ptr::null_mut();
+        let mut is_done = false;
+        unsafe {
+            ok(sys::JsConvertValueToBoolean(done.raw, &mut done_bool))?;
+            ok(sys::JsBooleanToBool(done_bool, &mut is_done))?;
+        }
+        if is_done {
+            return Ok(None);
+        }
+        Ok(Some(result.get(guard, "value")?))
+    }
+}
+
+// Strings are iterable but not objects; box them so the protocol applies.
+fn as_object<'a>(guard: &'a Guard<'_>, v: &Value<'_>) -> Result<Object<'a>> {
+    let mut out: sys::JsValueRef = std::ptr::null_mut();
+    unsafe { ok(sys::JsConvertValueToObject(v.raw, &mut out))?; }
+    Value::from_raw(out)
+        .as_object(guard)
+        .ok_or_else(|| type_err(format!("expected an object, got {}", v.type_name(guard))))
+}
+
+impl<'g> Iterator for JsIterator<'g, '_> {
+    type Item = Result<Value<'g>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.step() {
+            Ok(Some(v)) => Some(Ok(v)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl Drop for JsIterator<'_, '_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        let guard = self.guard;
+        // Errors, including a throwing `return()`, are ignored: there is no one to report them to.
+        let close = || -> Result<()> {
+            if let Some(f) = as_object(guard, &self.iterator)?.get(guard, "return")?.as_function(guard) {
+                f.call_with_this(guard, &self.iterator, &[])?;
+            }
+            Ok(())
+        };
+        let _ = close();
+    }
+}
+
+impl std::iter::FusedIterator for JsIterator<'_, '_> {}
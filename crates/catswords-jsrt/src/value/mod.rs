@@ -2,9 +2,37 @@ mod base;
 mod number;
 mod function;
 mod persistent;
+mod object;
+mod array;
+mod string;
+mod boolean;
+mod symbol;
+mod property_id;
+mod iter;
+mod buffer;
+mod convert;
+mod native;
+mod class;
 
 pub use base::Value;
 pub use number::Number;
 pub use function::{Function, CallInfo};
 pub use persistent::PersistentValue;
-pub(crate) use function::free_callback_state;
\ No newline at end of file
+pub use object::{Object, PropertyDescriptor};
+pub use property_id::{PropertyId, PropertyKey};
+pub use iter::JsIterator;
+pub use buffer::{ArrayBuffer, DataView, TypedArray, TypedArrayElement};
+pub use array::Array;
+pub use string::JsString;
+pub use boolean::Boolean;
+pub use symbol::Symbol;
+pub use convert::{FromJs, IntoJs};
+#[doc(hidden)]
+pub use convert::within;
+pub use native::{NativeFn, Param, Rest};
+#[doc(hidden)]
+pub use native::{check_arity, rest_is_last};
+pub use class::{Class, InstanceMut, InstanceRef, JsClass, JsMethods};
+#[cfg(feature = "macros")]
+pub use catswords_jsrt_macros::{FromJs, IntoJs};
+pub(crate) use function::{apply_panic_policy, report_panic, take_callback_state};
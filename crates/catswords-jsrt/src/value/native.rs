@@ -0,0 +1,145 @@
+use crate::error::{type_err, Error, Result};
+use crate::guard::Guard;
+use crate::value::{FromJs, IntoJs, Value};
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+// Collects every remaining argument. Must be the last parameter of a wrapped
+// closure; `Function::wrap` and `#[js_function]` fail to build otherwise.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rest<T>(pub Vec<T>);
+
+impl<T> Deref for Rest<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Rest<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+// One parameter of a closure passed to `Function::wrap`.
+pub trait Param: Sized {
+    // Whether a call must supply this argument (false for Option<T> and Rest<T>).
+    const REQUIRED: bool;
+    // Whether this parameter consumes all remaining arguments (only Rest<T>).
+    const REST: bool = false;
+
+    fn take<'g>(guard: &'g Guard<'_>, args: &[Value<'g>], index: &mut usize) -> Result<Self>;
+}
+
+fn argument_err(e: Error, index: usize) -> Error {
+    match e {
+        Error::Type(msg) => Error::Type(Cow::Owned(format!("argument {}: {}", index, msg))),
+        other => other,
+    }
+}
+
+// Parameters are owned Rust values (or wrappers that work for any guard
+// lifetime); raw `Value`s need `Function::new`.
+impl<T: for<'g> FromJs<'g>> Param for T {
+    const REQUIRED: bool = !<T as FromJs<'static>>::OPTIONAL;
+
+    fn take<'g>(guard: &'g Guard<'_>, args: &[Value<'g>], index: &mut usize) -> Result<Self> {
+        let i = *index;
+        *index += 1;
+        let v = match args.get(i) {
+            Some(v) => *v,
+            None => Value::undefined(guard)?,
+        };
+        T::from_js(guard, &v).map_err(|e| argument_err(e, i))
+    }
+}
+
+impl<T: for<'g> FromJs<'g>> Param for Rest<T> {
+    const REQUIRED: bool = false;
+    const REST: bool = true;
+
+    fn take<'g>(guard: &'g Guard<'_>, args: &[Value<'g>], index: &mut usize) -> Result<Self> {
+        let start = (*index).min(args.len());
+        *index = args.len();
+        args[start..]
+            .iter()
+            .enumerate()
+            .map(|(n, v)| T::from_js(guard, v).map_err(|e| argument_err(e, start + n)))
+            .collect::<Result<Vec<T>>>()
+            .map(Rest)
+    }
+}
+
+// `required` holds `Param::REQUIRED` of each parameter in order. Also used by
+// the code `catswords-jsrt-macros` generates.
+#[doc(hidden)]
+pub fn check_arity(got: usize, required: &[bool]) -> Result<()> {
+    // Position of the last required parameter; optional ones may only trail.
+    let required = required.iter().rposition(|r| *r).map_or(0, |i| i + 1);
+    if got < required {
+        return Err(type_err(format!(
+            "expected at least {} argument{}, got {}",
+            required,
+            if required == 1 { "" } else { "s" },
+            got
+        )));
+    }
+    Ok(())
+}
+
+// `rest` holds `Param::REST` of each parameter in order. Evaluated in a const
+// block, so a `Rest<T>` before the last parameter fails to compile.
+#[doc(hidden)]
+pub const fn rest_is_last(rest: &[bool]) -> bool {
+    let mut i = 0;
+    while i + 1 < rest.len() {
+        if rest[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// Implemented for `Fn(A1, .., An) -> Result<R>` closures of up to 12 parameters.
+// `Args` is the parameter tuple; it only exists to keep the impls apart.
+pub trait NativeFn<Args>: Send + 'static {
+    fn invoke<'g>(&self, guard: &'g Guard<'_>, args: &[Value<'g>]) -> Result<Value<'g>>;
+}
+
+macro_rules! native_fn {
+    ($($a:ident),*) => {
+        impl<F, R, $($a,)*> NativeFn<($($a,)*)> for F
+        where
+            F: Fn($($a),*) -> Result<R> + Send + 'static,
+            R: for<'g> IntoJs<'g>,
+            $($a: Param,)*
+        {
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn invoke<'g>(&self, guard: &'g Guard<'_>, args: &[Value<'g>]) -> Result<Value<'g>> {
+                const { assert!(rest_is_last(&[$(<$a as Param>::REST),*]), "Rest<T> must be the last parameter") };
+                check_arity(args.len(), &[$(<$a as Param>::REQUIRED),*])?;
+
+                let mut index = 0;
+                $(let $a = <$a as Param>::take(guard, args, &mut index)?;)*
+                (self)($($a),*)?.into_js(guard)
+            }
+        }
+    };
+}
+
+native_fn!();
+native_fn!(A1);
+native_fn!(A1, A2);
+native_fn!(A1, A2, A3);
+native_fn!(A1, A2, A3, A4);
+native_fn!(A1, A2, A3, A4, A5);
+native_fn!(A1, A2, A3, A4, A5, A6);
+native_fn!(A1, A2, A3, A4, A5, A6, A7);
+native_fn!(A1, A2, A3, A4, A5, A6, A7, A8);
+native_fn!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+native_fn!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+native_fn!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+native_fn!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
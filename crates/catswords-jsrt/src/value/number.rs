@@ -1,23 +1,65 @@
-use crate::error::ok;
+use crate::error::{ok, ok_msg, type_err, Result};
 use crate::guard::Guard;
 use crate::value::Value;
 use catswords_jsrt_sys as sys;
 
-pub struct Number {
-    v: Value,
+pub struct Number<'g> {
+    v: Value<'g>,
 }
 
-impl Number {
-    pub fn new(_guard: &Guard<'_>, n: i32) -> Self {
+impl<'g> Number<'g> {
+    pub fn new(_guard: &'g Guard<'_>, n: i32) -> Self {
         let mut out: sys::JsValueRef = std::ptr::null_mut();
         unsafe {
             // If this fails, it'll be a null pointer; for a minimal sample we keep it simple.
             let _ = ok(sys::JsIntToNumber(n, &mut out));
         }
-        Self { v: Value { raw: out } }
+        Self { v: Value::from_raw(out) }
     }
 
-    pub fn into(self) -> Value {
+    pub fn from_f64(_guard: &'g Guard<'_>, n: f64) -> Self {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            let _ = ok(sys::JsDoubleToNumber(n, &mut out));
+        }
+        Self { v: Value::from_raw(out) }
+    }
+
+    // JavaScript `Number(value)`: runs ToNumber on any value.
+    pub fn coerce(_guard: &'g Guard<'_>, value: &Value<'_>) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(sys::JsConvertValueToNumber(value.raw, &mut out), "JsConvertValueToNumber failed")?;
+        }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    pub fn value(&self, _guard: &Guard<'_>) -> Result<f64> {
+        let mut out: f64 = 0.0;
+        unsafe { ok(sys::JsNumberToDouble(self.v.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
         self.v
     }
 }
+
+// Accept `n` only if it is an integer within [min, max_exclusive).
+pub(crate) fn checked_integer(n: f64, min: f64, max_exclusive: f64, target: &str) -> Result<f64> {
+    if n.fract() != 0.0 || !n.is_finite() {
+        return Err(type_err(format!("{} is not an integer, cannot convert to {}", n, target)));
+    }
+    if n < min || n >= max_exclusive {
+        return Err(type_err(format!("{} is out of range for {}", n, target)));
+    }
+    Ok(n)
+}
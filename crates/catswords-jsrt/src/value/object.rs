@@ -0,0 +1,252 @@
+use crate::error::{ok, ok_msg, type_err, Result};
+use crate::guard::Guard;
+use crate::value::{Array, Boolean, Function, PropertyKey, Value};
+use catswords_jsrt_sys as sys;
+
+// Indices above i32::MAX do not fit JsIntToNumber, so go through a double.
+fn index_value(index: u32) -> Result<sys::JsValueRef> {
+    let mut out: sys::JsValueRef = std::ptr::null_mut();
+    unsafe { ok(sys::JsDoubleToNumber(index as f64, &mut out))?; }
+    Ok(out)
+}
+
+pub struct Object<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> Object<'g> {
+    pub fn new(_guard: &'g Guard<'_>) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsCreateObject(&mut out), "JsCreateObject failed")?; }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub fn global(_guard: &'g Guard<'_>) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsGetGlobalObject(&mut out), "JsGetGlobalObject failed")?; }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    pub fn get<K: PropertyKey + ?Sized>(&self, guard: &Guard<'_>, key: &K) -> Result<Value<'g>> {
+        let pid = key.property_id(guard)?;
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsGetProperty(self.v.raw, pid.raw, &mut out))?; }
+        Ok(Value::from_raw(out))
+    }
+
+    pub fn set<K: PropertyKey + ?Sized>(&self, guard: &Guard<'_>, key: &K, value: &Value<'_>) -> Result<()> {
+        let pid = key.property_id(guard)?;
+        unsafe { ok(sys::JsSetProperty(self.v.raw, pid.raw, value.raw, true)) }
+    }
+
+    // `key in object`: includes the prototype chain.
+    pub fn has<K: PropertyKey + ?Sized>(&self, guard: &Guard<'_>, key: &K) -> Result<bool> {
+        let pid = key.property_id(guard)?;
+        let mut out = false;
+        unsafe { ok(sys::JsHasProperty(self.v.raw, pid.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    pub fn has_own<K: PropertyKey + ?Sized>(&self, guard: &Guard<'_>, key: &K) -> Result<bool> {
+        let pid = key.property_id(guard)?;
+        let mut out = false;
+        unsafe { ok(sys::JsHasOwnProperty(self.v.raw, pid.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    // Returns false when the property is non-configurable and was not deleted.
+    pub fn delete<K: PropertyKey + ?Sized>(&self, guard: &Guard<'_>, key: &K) -> Result<bool> {
+        let pid = key.property_id(guard)?;
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsDeleteProperty(self.v.raw, pid.raw, false, &mut out))?; }
+        Boolean::from_value(Value::from_raw(out)).value(guard)
+    }
+
+    // `Object.defineProperty`. Returns whether the property was defined.
+    pub fn define_property<K: PropertyKey + ?Sized>(
+        &self,
+        guard: &Guard<'_>,
+        key: &K,
+        descriptor: &PropertyDescriptor<'_>,
+    ) -> Result<bool> {
+        let pid = key.property_id(guard)?;
+        let desc = descriptor.to_object(guard)?;
+        let mut out = false;
+        unsafe { ok(sys::JsDefineProperty(self.v.raw, pid.raw, desc.v.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    pub fn get_own_property_names(&self, _guard: &Guard<'_>) -> Result<Array<'g>> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsGetOwnPropertyNames(self.v.raw, &mut out))?; }
+        Ok(Array::from_value(Value::from_raw(out)))
+    }
+
+    pub fn get_own_property_symbols(&self, _guard: &Guard<'_>) -> Result<Array<'g>> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsGetOwnPropertySymbols(self.v.raw, &mut out))?; }
+        Ok(Array::from_value(Value::from_raw(out)))
+    }
+
+    // The prototype, or null.
+    pub fn get_prototype(&self, _guard: &Guard<'_>) -> Result<Value<'g>> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsGetPrototype(self.v.raw, &mut out))?; }
+        Ok(Value::from_raw(out))
+    }
+
+    // `prototype` must be an object or null.
+    pub fn set_prototype(&self, _guard: &Guard<'_>, prototype: &Value<'_>) -> Result<()> {
+        unsafe { ok(sys::JsSetPrototype(self.v.raw, prototype.raw)) }
+    }
+
+    pub fn is_extensible(&self, _guard: &Guard<'_>) -> Result<bool> {
+        let mut out = false;
+        unsafe { ok(sys::JsGetExtensionAllowed(self.v.raw, &mut out))?; }
+        Ok(out)
+    }
+
+    pub fn prevent_extensions(&self, _guard: &Guard<'_>) -> Result<()> {
+        unsafe { ok(sys::JsPreventExtension(self.v.raw)) }
+    }
+
+    // JsRT has no freeze call, so this goes through the global `Object.freeze`.
+    pub fn freeze(&self, guard: &Guard<'_>) -> Result<()> {
+        let object_ctor = Object::global(guard)?.get(guard, "Object")?;
+        let object_ctor = object_ctor
+            .as_object(guard)
+            .ok_or_else(|| type_err("global Object is missing".to_string()))?;
+        let freeze = object_ctor
+            .get(guard, "freeze")?
+            .as_function(guard)
+            .ok_or_else(|| type_err("Object.freeze is not a function".to_string()))?;
+        freeze.call_plain(guard, &[&self.v])?;
+        Ok(())
+    }
+
+    pub fn get_index(&self, _guard: &Guard<'_>, index: u32) -> Result<Value<'g>> {
+        let idx = index_value(index)?;
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok(sys::JsGetIndexedProperty(self.v.raw, idx, &mut out))?; }
+        Ok(Value::from_raw(out))
+    }
+
+    pub fn set_index(&self, _guard: &Guard<'_>, index: u32, value: &Value<'_>) -> Result<()> {
+        let idx = index_value(index)?;
+        unsafe { ok(sys::JsSetIndexedProperty(self.v.raw, idx, value.raw)) }
+    }
+
+    pub fn has_index(&self, _guard: &Guard<'_>, index: u32) -> Result<bool> {
+        let idx = index_value(index)?;
+        let mut out = false;
+        unsafe { ok(sys::JsHasIndexedProperty(self.v.raw, idx, &mut out))?; }
+        Ok(out)
+    }
+
+    pub fn delete_index(&self, _guard: &Guard<'_>, index: u32) -> Result<()> {
+        let idx = index_value(index)?;
+        unsafe { ok(sys::JsDeleteIndexedProperty(self.v.raw, idx)) }
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
+
+// A property descriptor for `Object::define_property`. Unset fields are left out,
+// so they take the usual defaults (false for the flags).
+#[derive(Default)]
+pub struct PropertyDescriptor<'g> {
+    value: Option<Value<'g>>,
+    get: Option<Value<'g>>,
+    set: Option<Value<'g>>,
+    writable: Option<bool>,
+    enumerable: Option<bool>,
+    configurable: Option<bool>,
+}
+
+impl<'g> PropertyDescriptor<'g> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn value(mut self, value: Value<'g>) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn getter(mut self, f: Function<'g>) -> Self {
+        self.get = Some(f.into());
+        self
+    }
+
+    pub fn setter(mut self, f: Function<'g>) -> Self {
+        self.set = Some(f.into());
+        self
+    }
+
+    // Getter backed by a Rust closure receiving `this`.
+    pub fn getter_fn<F>(self, guard: &'g Guard<'_>, f: F) -> Self
+    where
+        F: for<'a> Fn(&'a Guard<'_>, Value<'a>) -> Result<Value<'a>> + Send + 'static,
+    {
+        self.getter(Function::new(guard, Box::new(move |guard, info| f(guard, info.this))))
+    }
+
+    // Setter backed by a Rust closure receiving `this` and the assigned value.
+    pub fn setter_fn<F>(self, guard: &'g Guard<'_>, f: F) -> Self
+    where
+        F: for<'a> Fn(&'a Guard<'_>, Value<'a>, Value<'a>) -> Result<()> + Send + 'static,
+    {
+        self.setter(Function::new(guard, Box::new(move |guard, info| {
+            let value = match info.arguments.first() {
+                Some(v) => *v,
+                None => Value::undefined(guard)?,
+            };
+            f(guard, info.this, value)?;
+            Value::undefined(guard)
+        })))
+    }
+
+    pub fn writable(mut self, on: bool) -> Self {
+        self.writable = Some(on);
+        self
+    }
+
+    pub fn enumerable(mut self, on: bool) -> Self {
+        self.enumerable = Some(on);
+        self
+    }
+
+    pub fn configurable(mut self, on: bool) -> Self {
+        self.configurable = Some(on);
+        self
+    }
+
+    fn to_object<'a>(&self, guard: &'a Guard<'_>) -> Result<Object<'a>> {
+        let desc = Object::new(guard)?;
+        for (name, v) in [("value", self.value), ("get", self.get), ("set", self.set)] {
+            if let Some(v) = v {
+                desc.set(guard, name, &v)?;
+            }
+        }
+        for (name, flag) in [
+            ("writable", self.writable),
+            ("enumerable", self.enumerable),
+            ("configurable", self.configurable),
+        ] {
+            if let Some(flag) = flag {
+                desc.set(guard, name, &Boolean::new(guard, flag)?.into())?;
+            }
+        }
+        Ok(desc)
+    }
+}
@@ -1,27 +1,49 @@
-use crate::error::{ok_msg, Result};
+use crate::error::{err_msg, ok_msg, Result};
+use crate::guard::Guard;
 use crate::value::Value;
 use catswords_jsrt_sys as sys;
+use catswords_jsrt_sys::JsErrorCode;
+use crate::runtime::RuntimeShared;
+use std::sync::Weak;
 
+// A value kept alive (JsAddRef) independently of any guard. Read it back with
+// `get` under a guard of the same runtime. Dropping it after the runtime is gone
+// is fine; the reference simply died with the runtime. Dropping it on a thread
+// the runtime has since moved away from leaks the reference rather than race.
 pub struct PersistentValue {
     raw: sys::JsValueRef,
+    runtime: Weak<RuntimeShared>,
 }
 
 impl PersistentValue {
-    pub fn new(v: Value) -> Result<Self> {
+    pub fn new(guard: &Guard<'_>, v: &Value<'_>) -> Result<Self> {
+        unsafe { Self::from_raw(v.raw(), guard.runtime().liveness()) }
+    }
+
+    // `raw` must be a live value of the runtime behind `runtime`, on its thread.
+    pub(crate) unsafe fn from_raw(raw: sys::JsValueRef, runtime: Weak<RuntimeShared>) -> Result<Self> {
         let mut count: u32 = 0;
-        unsafe {
-            ok_msg(sys::JsAddRef(v.raw(), &mut count), "JsAddRef failed")?;
-        }
-        Ok(Self { raw: v.raw() })
+        ok_msg(sys::JsAddRef(raw, &mut count), "JsAddRef failed")?;
+        Ok(Self { raw, runtime })
     }
 
-    pub fn as_value(&self) -> Value {
-        Value { raw: self.raw }
+    pub fn get<'g>(&self, guard: &'g Guard<'_>) -> Result<Value<'g>> {
+        if !Weak::ptr_eq(&self.runtime, &guard.runtime().liveness()) {
+            return Err(err_msg(
+                JsErrorCode::JsErrorWrongRuntime,
+                "persistent value belongs to a different runtime".to_string(),
+            ));
+        }
+        Ok(Value::from_raw(self.raw))
     }
 }
 
 impl Drop for PersistentValue {
     fn drop(&mut self) {
+        match self.runtime.upgrade() {
+            Some(shared) if shared.on_owner_thread() => {}
+            _ => return,
+        }
         let mut count: u32 = 0;
         unsafe {
             let _ = sys::JsRelease(self.raw, &mut count);
@@ -0,0 +1,74 @@
+use crate::error::{ok_msg, Result};
+use crate::guard::Guard;
+use crate::value::Symbol;
+use catswords_jsrt_sys as sys;
+use std::ffi::c_char;
+use std::marker::PhantomData;
+
+// An interned property key. Create it once and reuse it for repeated lookups
+// instead of passing `&str`, which interns the name again on every call.
+#[derive(Clone, Copy, Debug)]
+pub struct PropertyId<'g> {
+    pub(crate) raw: sys::JsPropertyIdRef,
+    _guard: PhantomData<&'g ()>,
+}
+
+impl<'g> PropertyId<'g> {
+    pub fn new(_guard: &'g Guard<'_>, name: &str) -> Result<Self> {
+        let mut pid: sys::JsPropertyIdRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsCreatePropertyId(name.as_ptr() as *const c_char, name.len(), &mut pid),
+                "JsCreatePropertyId failed",
+            )?;
+        }
+        Ok(Self { raw: pid, _guard: PhantomData })
+    }
+
+    pub fn from_symbol(_guard: &'g Guard<'_>, symbol: &Symbol<'_>) -> Result<Self> {
+        let mut pid: sys::JsPropertyIdRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsGetPropertyIdFromSymbol(symbol.as_value().raw, &mut pid),
+                "JsGetPropertyIdFromSymbol failed",
+            )?;
+        }
+        Ok(Self { raw: pid, _guard: PhantomData })
+    }
+
+    pub fn raw(&self) -> sys::JsPropertyIdRef {
+        self.raw
+    }
+}
+
+// Anything that names a property: string names, symbols or a cached PropertyId.
+pub trait PropertyKey {
+    fn property_id<'g>(&self, guard: &'g Guard<'_>) -> Result<PropertyId<'g>>;
+}
+
+impl PropertyKey for str {
+    fn property_id<'g>(&self, guard: &'g Guard<'_>) -> Result<PropertyId<'g>> {
+        PropertyId::new(guard, self)
+    }
+}
+
+impl PropertyKey for String {
+    fn property_id<'g>(&self, guard: &'g Guard<'_>) -> Result<PropertyId<'g>> {
+        PropertyId::new(guard, self)
+    }
+}
+
+// Re-borrowing for `'g` is fine: `self` keeps its own guard alive, and while it is,
+// `Context::make_current` refuses to make guards of any other runtime, so `guard`
+// belongs to the runtime the id was created in.
+impl PropertyKey for PropertyId<'_> {
+    fn property_id<'g>(&self, _guard: &'g Guard<'_>) -> Result<PropertyId<'g>> {
+        Ok(PropertyId { raw: self.raw, _guard: PhantomData })
+    }
+}
+
+impl PropertyKey for Symbol<'_> {
+    fn property_id<'g>(&self, guard: &'g Guard<'_>) -> Result<PropertyId<'g>> {
+        PropertyId::from_symbol(guard, self)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error::{ok, ok_msg, type_err, Result};
+use crate::guard::Guard;
+use crate::value::Value;
+use catswords_jsrt_sys as sys;
+
+pub struct JsString<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> JsString<'g> {
+    pub fn new(guard: &'g Guard<'_>, s: &str) -> Result<Self> {
+        Ok(Self { v: Value::string_utf8(guard, s)? })
+    }
+
+    pub fn from_utf16(_guard: &'g Guard<'_>, units: &[u16]) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(
+                sys::JsCreateStringUtf16(units.as_ptr(), units.len(), &mut out),
+                "JsCreateStringUtf16 failed",
+            )?;
+        }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    // JavaScript `String(value)`: runs ToString on any value.
+    pub fn coerce(_guard: &'g Guard<'_>, value: &Value<'_>) -> Result<Self> {
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe {
+            ok_msg(sys::JsConvertValueToString(value.raw, &mut out), "JsConvertValueToString failed")?;
+        }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    // Length in UTF-16 code units, as JavaScript's `length` reports it.
+    pub fn len(&self, _guard: &Guard<'_>) -> Result<usize> {
+        let mut out: i32 = 0;
+        unsafe { ok(sys::JsGetStringLength(self.v.raw, &mut out))?; }
+        Ok(out as usize)
+    }
+
+    pub fn is_empty(&self, guard: &Guard<'_>) -> Result<bool> {
+        Ok(self.len(guard)? == 0)
+    }
+
+    pub fn to_utf16(&self, guard: &Guard<'_>) -> Result<Vec<u16>> {
+        let len = self.len(guard)?;
+        let mut buf: Vec<u16> = vec![0; len];
+        let mut written: usize = 0;
+        unsafe {
+            ok_msg(
+                sys::JsCopyStringUtf16(self.v.raw, 0, len as i32, buf.as_mut_ptr(), &mut written),
+                "JsCopyStringUtf16 failed",
+            )?;
+        }
+        buf.truncate(written);
+        Ok(buf)
+    }
+
+    // Unpaired surrogates are replaced with U+FFFD.
+    pub fn to_string_lossy(&self, guard: &Guard<'_>) -> Result<String> {
+        Ok(String::from_utf16_lossy(&self.to_utf16(guard)?))
+    }
+
+    // Fails on unpaired surrogates instead of replacing them.
+    pub fn to_rust_string(&self, guard: &Guard<'_>) -> Result<String> {
+        String::from_utf16(&self.to_utf16(guard)?)
+            .map_err(|_| type_err("string contains unpaired UTF-16 surrogates".to_string()))
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
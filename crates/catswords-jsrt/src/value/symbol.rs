@@ -0,0 +1,32 @@
+use crate::error::{ok_msg, Result};
+use crate::guard::Guard;
+use crate::value::Value;
+use catswords_jsrt_sys as sys;
+
+pub struct Symbol<'g> {
+    v: Value<'g>,
+}
+
+impl<'g> Symbol<'g> {
+    pub fn new(guard: &'g Guard<'_>, description: Option<&str>) -> Result<Self> {
+        let description = match description {
+            Some(d) => Value::string_utf8(guard, d)?,
+            None => Value::undefined(guard)?,
+        };
+        let mut out: sys::JsValueRef = std::ptr::null_mut();
+        unsafe { ok_msg(sys::JsCreateSymbol(description.raw, &mut out), "JsCreateSymbol failed")?; }
+        Ok(Self { v: Value::from_raw(out) })
+    }
+
+    pub(crate) fn from_value(v: Value<'g>) -> Self {
+        Self { v }
+    }
+
+    pub fn as_value(&self) -> &Value<'g> {
+        &self.v
+    }
+
+    pub fn into(self) -> Value<'g> {
+        self.v
+    }
+}
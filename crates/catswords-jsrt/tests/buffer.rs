@@ -0,0 +1,87 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::{ArrayBuffer, DataView, TypedArray};
+use catswords_jsrt::{script, Error, JsTypedArrayType};
+use common::with_guard;
+
+#[test]
+fn boxed_buffer_is_visible_to_script() {
+    with_guard(|guard| {
+        let buffer = ArrayBuffer::from_vec(guard, vec![1, 2, 3, 4]).unwrap();
+        guard.context().set_global("buf", buffer.as_value()).unwrap();
+        let sum = script::eval(guard, "new Uint8Array(buf).reduce((a, b) => a + b, 0)").unwrap();
+        assert_eq!(sum.to_integer(guard).unwrap(), 10);
+
+        script::eval(guard, "new Uint8Array(buf)[0] = 9").unwrap();
+        assert_eq!(buffer.to_vec(guard).unwrap(), [9, 2, 3, 4]);
+    });
+}
+
+#[test]
+fn copy_in_and_out() {
+    with_guard(|guard| {
+        let buffer = ArrayBuffer::new(guard, 8).unwrap();
+        assert_eq!(buffer.to_vec(guard).unwrap(), [0; 8]);
+        buffer.copy_from_slice(guard, &[7; 8]).unwrap();
+        assert!(matches!(buffer.copy_from_slice(guard, &[0; 3]), Err(Error::Type(_))));
+
+        let floats = TypedArray::new(guard, JsTypedArrayType::JsArrayTypeFloat32, &buffer, 0, 2).unwrap();
+        floats.copy_from_slice(guard, &[1.5f32, -2.0]).unwrap();
+        assert_eq!(floats.to_vec::<f32>(guard).unwrap(), [1.5, -2.0]);
+        assert_eq!(unsafe { floats.as_slice::<f32>(guard) }.unwrap(), [1.5, -2.0]);
+        assert_eq!(floats.to_byte_vec(guard).unwrap().len(), 8);
+        assert!(matches!(floats.to_vec::<i32>(guard), Err(Error::Type(_))));
+
+        let view = DataView::new(guard, &buffer, 4, 4).unwrap();
+        assert_eq!(view.to_vec(guard).unwrap(), (-2.0f32).to_ne_bytes());
+    });
+}
+
+#[test]
+fn mutable_views() {
+    with_guard(|guard| {
+        let mut array = TypedArray::with_len(guard, JsTypedArrayType::JsArrayTypeInt32, 3).unwrap();
+        unsafe { array.as_mut_slice::<i32>(guard).unwrap() }.copy_from_slice(&[1, 2, 3]);
+        guard.context().set_global("arr", array.as_value()).unwrap();
+        let v = script::eval(guard, "arr[0] + arr[1] + arr[2]").unwrap();
+        assert_eq!(v.to_integer(guard).unwrap(), 6);
+    });
+}
+
+#[test]
+fn buffers_from_script() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "new Uint16Array([1, 2, 3])").unwrap();
+        let array = v.as_typed_array(guard).unwrap();
+        assert_eq!(array.kind(guard).unwrap(), JsTypedArrayType::JsArrayTypeUint16);
+        assert_eq!(array.byte_len(guard).unwrap(), 6);
+        assert_eq!(array.buffer(guard).unwrap().len(guard).unwrap(), 6);
+        assert!(v.as_array_buffer(guard).is_none());
+    });
+}
+
+#[test]
+fn detached_buffers_are_errors() {
+    with_guard(|guard| {
+        let buffer = ArrayBuffer::from_vec(guard, vec![1, 2, 3, 4]).unwrap();
+        let array = TypedArray::new(guard, JsTypedArrayType::JsArrayTypeUint8, &buffer, 0, 4).unwrap();
+        let view = DataView::new(guard, &buffer, 0, 4).unwrap();
+        assert!(!buffer.is_detached(guard).unwrap());
+
+        buffer.detach(guard).unwrap();
+        assert!(buffer.is_detached(guard).unwrap());
+        assert!(matches!(buffer.to_vec(guard), Err(Error::Type(_))));
+        assert!(matches!(unsafe { buffer.as_bytes(guard) }, Err(Error::Type(_))));
+        assert!(matches!(buffer.copy_from_slice(guard, &[]), Err(Error::Type(_))));
+        assert!(matches!(array.to_vec::<u8>(guard), Err(Error::Type(_))));
+        assert!(matches!(unsafe { array.as_slice::<u8>(guard) }, Err(Error::Type(_))));
+        assert!(matches!(view.to_vec(guard), Err(Error::Type(_))));
+
+        // Empty buffers are not detached ones.
+        let empty = ArrayBuffer::new(guard, 0).unwrap();
+        assert!(!empty.is_detached(guard).unwrap());
+        assert_eq!(empty.to_vec(guard).unwrap(), []);
+    });
+}
@@ -0,0 +1,219 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::{CallInfo, Class, FromJs, IntoJs, JsClass, JsMethods, Value};
+use catswords_jsrt::{script, type_err, Guard, Result};
+use catswords_jsrt_sys as sys;
+use common::with_guard;
+use std::any::TypeId;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Counter {
+    count: i32,
+}
+
+struct Other;
+
+fn define(guard: &Guard<'_>) {
+    let counter = Class::<Counter>::new(guard, "Counter", |guard, info| {
+        let start = match info.arguments.first() {
+            Some(v) => i32::from_js(guard, v)?,
+            None => 0,
+        };
+        Ok(Counter { count: start })
+    })
+    .unwrap()
+    .method_mut("bump", |guard, c, _info| {
+        c.count += 1;
+        c.count.into_js(guard)
+    })
+    .unwrap()
+    .getter("count", |guard, c| c.count.into_js(guard))
+    .unwrap()
+    .method_mut("reenter", |guard, _c, info| {
+        let f = info.arguments[0].as_function(guard).unwrap();
+        f.call(guard, &[])
+    })
+    .unwrap()
+    .build()
+    .unwrap();
+    guard.context().set_global("Counter", counter.as_value()).unwrap();
+
+    let other = Class::<Other>::new(guard, "Other", |_, _| Ok(Other)).unwrap().build().unwrap();
+    guard.context().set_global("Other", other.as_value()).unwrap();
+}
+
+fn eval_string(guard: &Guard<'_>, code: &str) -> String {
+    script::eval(guard, code).unwrap().to_rust_string(guard).unwrap()
+}
+
+#[test]
+fn instances_and_subclasses() {
+    with_guard(|guard| {
+        define(guard);
+        let v = script::eval(guard, "const c = new Counter(5); c.bump(); c.bump(); c").unwrap();
+        assert_eq!(v.downcast_ref::<Counter>(guard).unwrap().count, 7);
+        assert!(v.downcast_ref::<Other>(guard).is_err());
+
+        let v = script::eval(guard, "class Sub extends Counter { twice() { this.bump(); return this.bump(); } } new Sub().twice()").unwrap();
+        assert_eq!(v.to_integer(guard).unwrap(), 2);
+    });
+}
+
+#[test]
+fn foreign_receivers_throw_type_errors() {
+    with_guard(|guard| {
+        define(guard);
+        let check = |code: &str| {
+            let wrapped = format!("try {{ {}; 'no error' }} catch (e) {{ e instanceof TypeError ? 'TypeError' : String(e) }}", code);
+            assert_eq!(eval_string(guard, &wrapped), "TypeError", "{}", code);
+        };
+        check("Counter.prototype.bump.call({})");
+        check("Counter.prototype.bump.call(new Other())");
+        check("Counter.prototype.bump.call(42)");
+        check("Counter.prototype.bump.call(undefined)");
+        check("Object.getOwnPropertyDescriptor(Counter.prototype, 'count').get.call([])");
+        check("Counter(1)");
+        check("const c = new Counter(); c.reenter(() => c.bump())");
+
+        let plain = script::eval(guard, "({})").unwrap();
+        assert!(plain.downcast_mut::<Counter>(guard).is_err());
+        assert!(Value::undefined(guard).unwrap().downcast_ref::<Counter>(guard).is_err());
+    });
+}
+
+// An external object made outside of `Class`, with data that starts like an
+// instance of `Counter` would.
+#[test]
+fn foreign_external_objects_are_not_instances() {
+    with_guard(|guard| {
+        let data = Box::into_raw(Box::new((TypeId::of::<Counter>(), [0u8; 64])));
+        unsafe extern "C" fn free(data: *mut c_void) {
+            drop(Box::from_raw(data as *mut (TypeId, [u8; 64])));
+        }
+        unsafe {
+            let mut object: sys::JsValueRef = std::ptr::null_mut();
+            assert_eq!(sys::JsCreateExternalObject(data as *mut c_void, Some(free), &mut object), sys::JsErrorCode::JsNoError);
+            let mut global: sys::JsValueRef = std::ptr::null_mut();
+            assert_eq!(sys::JsGetGlobalObject(&mut global), sys::JsErrorCode::JsNoError);
+            let mut pid: sys::JsPropertyIdRef = std::ptr::null_mut();
+            let name = "foreign";
+            assert_eq!(sys::JsCreatePropertyId(name.as_ptr() as *const _, name.len(), &mut pid), sys::JsErrorCode::JsNoError);
+            assert_eq!(sys::JsSetProperty(global, pid, object, true), sys::JsErrorCode::JsNoError);
+        }
+
+        define(guard);
+        let foreign = script::eval(guard, "foreign").unwrap();
+        assert!(foreign.downcast_ref::<Counter>(guard).is_err());
+        assert!(foreign.downcast_mut::<Counter>(guard).is_err());
+        let msg = eval_string(guard, "try { Counter.prototype.bump.call(foreign) } catch (e) { e.name }");
+        assert_eq!(msg, "TypeError");
+    });
+}
+
+struct Point {
+    x: i32,
+}
+
+#[test]
+fn accessors_and_statics() {
+    with_guard(|guard| {
+        let point = Class::<Point>::new(guard, "Point", |guard, info| Ok(Point { x: i32::from_js(guard, &info.arguments[0])? }))
+            .unwrap()
+            .accessor("x", |guard, p| p.x.into_js(guard), |guard, p, v| {
+                p.x = i32::from_js(guard, &v)?;
+                Ok(())
+            })
+            .unwrap()
+            .getter("double", |guard, p| (p.x * 2).into_js(guard))
+            .unwrap()
+            .static_method("origin", |guard, _info| script::eval(guard, "new Point(0)"))
+            .unwrap()
+            .static_value("dimensions", &1.into_js(guard).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        guard.context().set_global("Point", point.as_value()).unwrap();
+
+        let v = script::eval(guard, "const p = new Point(2); p.x = 5; p").unwrap();
+        assert_eq!(v.downcast_ref::<Point>(guard).unwrap().x, 5);
+        assert_eq!(eval_string(guard, "[p.x, p.double, Point.origin().x, Point.dimensions].join()"), "5,10,0,1");
+        assert_eq!(eval_string(guard, "'use strict'; try { p.double = 1; 'assigned' } catch (e) { e.name }"), "TypeError");
+        assert_eq!(eval_string(guard, "try { p.x = 'five'; 'assigned' } catch (e) { e.name }"), "TypeError");
+        assert_eq!(eval_string(guard, "Object.keys(p).length + ',' + Object.keys(Point).length"), "0,0");
+    });
+}
+
+struct Opaque;
+
+impl JsClass for Opaque {
+    const NAME: &'static str = "Opaque";
+}
+
+impl JsMethods for Opaque {
+    fn construct<'a>(_guard: &'a Guard<'_>, _info: &CallInfo<'a>) -> Result<Self> {
+        Err(type_err("Opaque cannot be constructed from script".to_string()))
+    }
+
+    fn members<'g>(class: Class<'g, Self>) -> Result<Class<'g, Self>> {
+        class.getter("kind", |guard, _| "opaque".into_js(guard))
+    }
+}
+
+#[test]
+fn classes_without_a_constructor() {
+    with_guard(|guard| {
+        let opaque = Class::<Opaque>::define(guard).unwrap();
+        guard.context().set_global("Opaque", opaque.as_value()).unwrap();
+        let msg = eval_string(guard, "try { new Opaque(); 'constructed' } catch (e) { e.name + ': ' + e.message }");
+        assert_eq!(msg, "TypeError: Opaque cannot be constructed from script");
+        assert_eq!(eval_string(guard, "typeof Opaque.prototype.constructor"), "function");
+    });
+}
+
+struct Tracked(Arc<AtomicUsize>);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn define_tracked(guard: &Guard<'_>, drops: &Arc<AtomicUsize>) {
+    let drops = drops.clone();
+    let tracked = Class::<Tracked>::new(guard, "Tracked", move |_, _| Ok(Tracked(drops.clone()))).unwrap().build().unwrap();
+    guard.context().set_global("Tracked", tracked.as_value()).unwrap();
+}
+
+// Kept out of line so no stack slot of the caller still points at the objects.
+#[inline(never)]
+fn make_garbage(guard: &Guard<'_>) {
+    script::eval(guard, "for (let i = 0; i < 10; i++) new Tracked()").unwrap();
+}
+
+#[test]
+fn instances_are_dropped_when_collected() {
+    with_guard(|guard| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        define_tracked(guard, &drops);
+        make_garbage(guard);
+        guard.runtime().collect_garbage().unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 10);
+    });
+}
+
+#[test]
+fn borrows_keep_the_instance_alive() {
+    with_guard(|guard| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        define_tracked(guard, &drops);
+        let borrowed = script::eval(guard, "new Tracked()").unwrap().downcast_ref::<Tracked>(guard).unwrap();
+        guard.runtime().collect_garbage().unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert!(Arc::ptr_eq(&borrowed.0, &drops));
+        drop(borrowed);
+    });
+}
@@ -0,0 +1,15 @@
+// Every test under tests/ runs script, so it needs a linkable ChakraCore. They
+// only build with the `jsrt_engine_tests` cfg:
+//
+//     RUSTFLAGS="--cfg jsrt_engine_tests" cargo test -p catswords-jsrt --features serde
+#![allow(dead_code)]
+
+use catswords_jsrt::{Context, Guard, Runtime};
+
+// Runs `f` inside a fresh runtime and context.
+pub fn with_guard<R>(f: impl FnOnce(&Guard<'_>) -> R) -> R {
+    let runtime = Runtime::new().expect("runtime");
+    let context = Context::new(&runtime).expect("context");
+    let guard = context.make_current().expect("make_current");
+    f(&guard)
+}
@@ -0,0 +1,42 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::{FromJs, IntoJs};
+use catswords_jsrt::{script, Error};
+use common::with_guard;
+
+#[test]
+fn integer_bounds() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "2 ** 63").unwrap();
+        assert!(matches!(i64::from_js(guard, &v), Err(Error::Type(_))));
+        assert_eq!(u64::from_js(guard, &v).unwrap(), 1 << 63);
+
+        let v = script::eval(guard, "-(2 ** 63)").unwrap();
+        assert_eq!(i64::from_js(guard, &v).unwrap(), i64::MIN);
+
+        let v = script::eval(guard, "2 ** 32").unwrap();
+        assert!(matches!(u32::from_js(guard, &v), Err(Error::Type(_))));
+        assert_eq!(usize::from_js(guard, &v).is_ok(), usize::BITS > 32);
+
+        let v = script::eval(guard, "1.5").unwrap();
+        assert!(matches!(i32::from_js(guard, &v), Err(Error::Type(_))));
+    });
+}
+
+#[test]
+fn wide_integers_round_trip() {
+    with_guard(|guard| {
+        let v = (-(1i128 << 100)).into_js(guard).unwrap();
+        assert_eq!(i128::from_js(guard, &v).unwrap(), -(1i128 << 100));
+
+        let v = (1u128 << 127).into_js(guard).unwrap();
+        assert_eq!(u128::from_js(guard, &v).unwrap(), 1u128 << 127);
+
+        let v = script::eval(guard, "2 ** 128").unwrap();
+        assert!(matches!(u128::from_js(guard, &v), Err(Error::Type(_))));
+        let v = script::eval(guard, "-1").unwrap();
+        assert!(matches!(u128::from_js(guard, &v), Err(Error::Type(_))));
+    });
+}
@@ -0,0 +1,46 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::{script, Error, JsErrorCode};
+use common::with_guard;
+
+#[test]
+fn type_error_metadata() {
+    with_guard(|guard| {
+        let err = script::eval(guard, "null.x").unwrap_err();
+        let Error::JsException(e) = err else { panic!("expected a JsException, got {err:?}") };
+        assert_eq!(e.code, JsErrorCode::JsErrorScriptException);
+        assert_eq!(e.name.as_deref(), Some("TypeError"));
+        assert!(e.stack.is_some());
+    });
+}
+
+#[test]
+fn syntax_error_position() {
+    with_guard(|guard| {
+        let err = script::eval(guard, "let x = ;").unwrap_err();
+        let Error::JsException(e) = err else { panic!("expected a JsException, got {err:?}") };
+        assert_eq!(e.code, JsErrorCode::JsErrorScriptCompile);
+        assert_eq!(e.line, Some(0));
+        assert!(e.column.is_some());
+    });
+}
+
+#[test]
+fn thrown_value_survives_collection() {
+    with_guard(|guard| {
+        let err = script::eval(guard, "throw { answer: 42 }").unwrap_err();
+        let Error::JsException(e) = err else { panic!("expected a JsException, got {err:?}") };
+        guard.runtime().collect_garbage().unwrap();
+        let thrown = e.value(guard).unwrap().as_object(guard).unwrap();
+        assert_eq!(thrown.get(guard, "answer").unwrap().to_integer(guard).unwrap(), 42);
+    });
+}
+
+#[test]
+fn thrown_value_needs_its_own_runtime() {
+    let err = with_guard(|guard| script::eval(guard, "throw 1").unwrap_err());
+    let Error::JsException(e) = err else { panic!("expected a JsException, got {err:?}") };
+    with_guard(|guard| assert!(e.value(guard).is_err()));
+}
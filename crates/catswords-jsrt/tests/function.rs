@@ -0,0 +1,224 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::{FromJs, Function, IntoJs, PersistentValue, Rest, Value};
+use catswords_jsrt::{script, Context, Error, Result, Runtime};
+use common::with_guard;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn make_add(runtime: &Runtime) -> PersistentValue {
+    let context = Context::new(runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    let add = Function::wrap(&guard, |a: i32, b: i32| -> Result<i32> { Ok(a + b) });
+    PersistentValue::new(&guard, add.as_value()).unwrap()
+}
+
+fn call_add(runtime: &Runtime, add: &PersistentValue) -> i32 {
+    let context = Context::new(runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    let add = add.get(&guard).unwrap();
+    context.set_global("add", &add).unwrap();
+    script::eval(&guard, "add(2, 3)").unwrap().to_integer(&guard).unwrap()
+}
+
+#[test]
+fn callbacks_survive_a_moved_runtime() {
+    let runtime = Runtime::new().unwrap();
+    let add = make_add(&runtime);
+    let moved = Box::new(runtime);
+    assert_eq!(call_add(&moved, &add), 5);
+}
+
+#[test]
+fn errors_become_exceptions() {
+    with_guard(|guard| {
+        let fail = Function::wrap(guard, |s: String| -> Result<i32> { Err(catswords_jsrt::type_err(s)) });
+        guard.context().set_global("fail", fail.as_value()).unwrap();
+        let v = script::eval(guard, "try { fail('nope') } catch (e) { e instanceof TypeError && e.message }").unwrap();
+        assert_eq!(v.to_rust_string(guard).unwrap(), "nope");
+
+        let err = script::eval(guard, "fail(1)").unwrap_err();
+        assert!(matches!(err, Error::JsException(_)));
+    });
+}
+
+#[test]
+fn panics_become_exceptions() {
+    with_guard(|guard| {
+        let boom = Function::wrap(guard, || -> Result<()> { panic!("boom") });
+        guard.context().set_global("boom", boom.as_value()).unwrap();
+        let v = script::eval(guard, "try { boom() } catch (e) { e.message }").unwrap();
+        assert!(v.to_rust_string(guard).unwrap().contains("boom"));
+    });
+}
+
+#[test]
+fn wrap_checks_arity() {
+    with_guard(|guard| {
+        let add = Function::wrap(guard, |a: i32, b: i32| -> Result<i32> { Ok(a + b) });
+        guard.context().set_global("add", add.as_value()).unwrap();
+        assert_eq!(script::eval(guard, "add(2, 3, 4)").unwrap().to_integer(guard).unwrap(), 5);
+
+        let v = script::eval(guard, "try { add(1) } catch (e) { e instanceof TypeError && e.message }").unwrap();
+        assert_eq!(v.to_rust_string(guard).unwrap(), "expected at least 2 arguments, got 1");
+    });
+}
+
+#[test]
+fn wrap_optional_and_rest_parameters() {
+    with_guard(|guard| {
+        let greet = Function::wrap(guard, |name: String, greeting: Option<String>| -> Result<String> {
+            Ok(format!("{}, {}", greeting.as_deref().unwrap_or("hello"), name))
+        });
+        guard.context().set_global("greet", greet.as_value()).unwrap();
+        let v = script::eval(guard, "greet('a') + '|' + greet('b', 'hi') + '|' + greet('c', undefined)").unwrap();
+        assert_eq!(v.to_rust_string(guard).unwrap(), "hello, a|hi, b|hello, c");
+
+        let sum = Function::wrap(guard, |first: i32, rest: Rest<i32>| -> Result<i32> {
+            Ok(first + rest.iter().sum::<i32>())
+        });
+        guard.context().set_global("sum", sum.as_value()).unwrap();
+        assert_eq!(script::eval(guard, "sum(1)").unwrap().to_integer(guard).unwrap(), 1);
+        assert_eq!(script::eval(guard, "sum(1, 2, 3, 4)").unwrap().to_integer(guard).unwrap(), 10);
+
+        // Conversion errors name the offending argument, counted from 0.
+        let v = script::eval(guard, "try { sum(1, 2, 'x') } catch (e) { e.message }").unwrap();
+        assert!(v.to_rust_string(guard).unwrap().starts_with("argument 2:"));
+    });
+}
+
+#[test]
+fn call_info_this_callee_and_new_target() {
+    with_guard(|guard| {
+        let inspect = Function::new(
+            guard,
+            Box::new(|guard, info| {
+                let report = format!(
+                    "{}:{}:{}:{}",
+                    info.this.type_name(guard),
+                    info.callee.as_function(guard).is_some(),
+                    info.is_construct_call,
+                    info.new_target.type_name(guard),
+                );
+                report.as_str().into_js(guard)
+            }),
+        );
+        guard.context().set_global("inspect", inspect.as_value()).unwrap();
+
+        let v = script::eval(guard, "({ m: inspect }).m()").unwrap();
+        assert_eq!(v.to_rust_string(guard).unwrap(), "object:true:false:undefined");
+
+        let v = script::eval(guard, "inspect.call([])").unwrap();
+        assert_eq!(v.to_rust_string(guard).unwrap(), "array:true:false:undefined");
+
+        // A construct call returns the object the engine created, not the string.
+        let v = script::eval(guard, "new inspect() instanceof inspect").unwrap();
+        assert!(bool::from_js(guard, &v).unwrap());
+
+        let callee = Function::new(guard, Box::new(|_, info| Ok(info.callee)));
+        guard.context().set_global("callee", callee.as_value()).unwrap();
+        assert!(bool::from_js(guard, &script::eval(guard, "callee() === callee").unwrap()).unwrap());
+    });
+}
+
+#[test]
+fn call_variants_set_this() {
+    with_guard(|guard| {
+        let this_of = script::eval(guard, "(function () { 'use strict'; return this; })").unwrap();
+        let this_of = this_of.as_function(guard).unwrap();
+
+        let same = |a: &Value<'_>, b: &Value<'_>| {
+            guard.context().set_global("a", a).unwrap();
+            guard.context().set_global("b", b).unwrap();
+            bool::from_js(guard, &script::eval(guard, "a === b").unwrap()).unwrap()
+        };
+
+        let v = this_of.call(guard, &[]).unwrap();
+        assert!(same(&v, this_of.as_value()));
+
+        let v = this_of.call_plain(guard, &[]).unwrap();
+        assert!(v.is_undefined(guard));
+
+        let receiver = script::eval(guard, "({ tag: 1 })").unwrap();
+        let v = this_of.call_with_this(guard, &receiver, &[]).unwrap();
+        assert!(same(&v, &receiver));
+    });
+}
+
+#[test]
+fn construct_runs_new() {
+    with_guard(|guard| {
+        let point = script::eval(guard, "(function Point(x) { this.x = x; this.direct = new.target === Point; })").unwrap();
+        let point = point.as_function(guard).unwrap();
+        let x = 4.into_js(guard).unwrap();
+        let p = point.construct(guard, &[&x]).unwrap();
+        guard.context().set_global("p", &p).unwrap();
+        let v = script::eval(guard, "p.x === 4 && p.direct").unwrap();
+        assert!(bool::from_js(guard, &v).unwrap());
+    });
+}
+
+struct CountDrops(Arc<AtomicUsize>);
+
+impl Drop for CountDrops {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct PanicOnDrop;
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        panic!("drop");
+    }
+}
+
+#[inline(never)]
+fn make_garbage(guard: &catswords_jsrt::Guard<'_>, dropped: &Arc<AtomicUsize>) {
+    for _ in 0..100 {
+        let token = CountDrops(dropped.clone());
+        Function::new(guard, Box::new(move |guard, _| {
+            let _ = &token;
+            Value::undefined(guard)
+        }));
+    }
+}
+
+#[test]
+fn collected_functions_drop_their_closures() {
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let runtime = Runtime::new().unwrap();
+    {
+        let context = Context::new(&runtime).unwrap();
+        let guard = context.make_current().unwrap();
+        make_garbage(&guard, &dropped);
+        runtime.collect_garbage().unwrap();
+        // The stack is scanned conservatively, so a few may survive this collection.
+        assert!(dropped.load(Ordering::SeqCst) > 0);
+    }
+    drop(runtime);
+    assert_eq!(dropped.load(Ordering::SeqCst), 100);
+}
+
+#[test]
+fn panicking_closure_drops_do_not_unwind() {
+    let runtime = Runtime::new().unwrap();
+    {
+        let context = Context::new(&runtime).unwrap();
+        let guard = context.make_current().unwrap();
+        for _ in 0..10 {
+            let token = PanicOnDrop;
+            Function::new(&guard, Box::new(move |guard, _| {
+                let _ = &token;
+                Value::undefined(guard)
+            }));
+        }
+        runtime.collect_garbage().unwrap();
+        assert_eq!(script::eval(&guard, "1").unwrap().to_integer(&guard).unwrap(), 1);
+    }
+    // Whatever the collection left behind is dropped with the runtime.
+    drop(runtime);
+}
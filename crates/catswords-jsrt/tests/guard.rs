@@ -0,0 +1,47 @@
+#![cfg(jsrt_engine_tests)]
+
+use catswords_jsrt::{script, Context, Error, JsErrorCode, Runtime};
+
+#[test]
+fn one_runtime_per_thread_at_a_time() {
+    let a = Runtime::new().unwrap();
+    let b = Runtime::new().unwrap();
+    let context_a = Context::new(&a).unwrap();
+    let context_b = Context::new(&b).unwrap();
+
+    {
+        let guard = context_a.make_current().unwrap();
+        let err = context_b.make_current().err().unwrap();
+        assert!(matches!(err, Error::Jsrt { code: JsErrorCode::JsErrorWrongRuntime, .. }));
+
+        // Another context of the same runtime is fine.
+        let other = Context::new(&a).unwrap();
+        let nested = other.make_current().unwrap();
+        drop(guard);
+        assert!(context_b.make_current().is_err());
+        drop(nested);
+    }
+
+    let guard = context_b.make_current().unwrap();
+    assert_eq!(script::eval(&guard, "1").unwrap().to_integer(&guard).unwrap(), 1);
+}
+
+#[test]
+fn values_from_another_context_are_detected() {
+    let runtime = Runtime::new().unwrap();
+    let context_a = Context::new(&runtime).unwrap();
+    let context_b = Context::new(&runtime).unwrap();
+
+    let guard_a = context_a.make_current().unwrap();
+    let object = script::eval(&guard_a, "({ n: 1 })").unwrap();
+    let number = script::eval(&guard_a, "1").unwrap();
+    object.check_context(&guard_a).unwrap();
+
+    let guard_b = context_b.make_current().unwrap();
+    let err = object.check_context(&guard_b).unwrap_err();
+    assert_eq!(err.code(), JsErrorCode::JsErrorInvalidContext);
+    number.check_context(&guard_b).unwrap();
+    drop(guard_b);
+
+    object.check_context(&guard_a).unwrap();
+}
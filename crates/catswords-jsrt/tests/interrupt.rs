@@ -0,0 +1,94 @@
+#![cfg(jsrt_engine_tests)]
+
+use catswords_jsrt::value::Function;
+use catswords_jsrt::{script, Context, Error, Runtime};
+use std::time::Duration;
+
+fn interruptible() -> Runtime {
+    Runtime::builder().allow_script_interrupt(true).build().unwrap()
+}
+
+#[test]
+fn timeout_terminates_and_recovers() {
+    let runtime = interruptible();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    let err = script::eval_with_timeout(&guard, "for (;;) {}", Duration::from_millis(50)).unwrap_err();
+    assert!(matches!(err, Error::ScriptTerminated));
+    assert!(!runtime.is_execution_disabled().unwrap());
+
+    let v = script::eval_with_timeout(&guard, "1 + 1", Duration::from_secs(5)).unwrap();
+    assert_eq!(v.to_integer(&guard).unwrap(), 2);
+}
+
+#[test]
+fn timeout_keeps_external_interrupt() {
+    let runtime = interruptible();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    runtime.interrupt_handle().unwrap().interrupt().unwrap();
+    let err = script::eval_with_timeout(&guard, "1", Duration::from_secs(5)).unwrap_err();
+    assert!(matches!(err, Error::ScriptTerminated));
+    assert!(runtime.is_execution_disabled().unwrap());
+
+    runtime.enable_execution().unwrap();
+    assert_eq!(script::eval(&guard, "3").unwrap().to_integer(&guard).unwrap(), 3);
+}
+
+#[test]
+fn interrupt_from_another_thread() {
+    let runtime = interruptible();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    let handle = runtime.interrupt_handle().unwrap();
+    let stopper = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        handle.interrupt().unwrap();
+    });
+    assert!(matches!(script::eval(&guard, "for (;;) {}"), Err(Error::ScriptTerminated)));
+    stopper.join().unwrap();
+}
+
+#[test]
+fn interrupt_after_drop_is_a_no_op() {
+    let handle = interruptible().interrupt_handle().unwrap();
+    assert!(!handle.is_runtime_alive());
+    handle.interrupt().unwrap();
+}
+
+#[test]
+fn many_timed_evals_share_one_watchdog() {
+    let runtime = interruptible();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    for i in 0..500 {
+        let v = script::eval_with_timeout(&guard, &i.to_string(), Duration::from_secs(5)).unwrap();
+        assert_eq!(v.to_integer(&guard).unwrap(), i);
+    }
+    let err = script::eval_with_timeout(&guard, "for (;;) {}", Duration::from_millis(20)).unwrap_err();
+    assert!(matches!(err, Error::ScriptTerminated));
+    assert_eq!(script::eval(&guard, "7").unwrap().to_integer(&guard).unwrap(), 7);
+}
+
+#[test]
+fn nested_timeout_terminates_the_whole_stack() {
+    let runtime = interruptible();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    let spin = Function::new(
+        &guard,
+        Box::new(|guard, _| script::eval_with_timeout(guard, "for (;;) {}", Duration::from_millis(20))),
+    );
+    context.set_global("spin", spin.as_value()).unwrap();
+
+    let err = script::eval_with_timeout(&guard, "try { spin() } catch (e) {} 'kept running'", Duration::from_secs(30))
+        .unwrap_err();
+    assert!(matches!(err, Error::ScriptTerminated));
+    assert!(!runtime.is_execution_disabled().unwrap());
+    assert_eq!(script::eval(&guard, "8").unwrap().to_integer(&guard).unwrap(), 8);
+}
@@ -0,0 +1,88 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::FromJs;
+use catswords_jsrt::{script, Error};
+use common::with_guard;
+
+#[test]
+fn map_entries() {
+    with_guard(|guard| {
+        let map = script::eval(guard, "new Map([['a', 1], ['b', 2]])").unwrap();
+        let entries: Vec<(String, i32)> = map
+            .iter(guard)
+            .unwrap()
+            .map(|entry| <(String, i32)>::from_js(guard, &entry.unwrap()).unwrap())
+            .collect();
+        assert_eq!(entries, [("a".to_string(), 1), ("b".to_string(), 2)]);
+    });
+}
+
+#[test]
+fn generator_and_string() {
+    with_guard(|guard| {
+        let gen = script::eval(guard, "(function* () { yield 1; yield 2; yield 3; })()").unwrap();
+        let sum: i32 = gen.iter(guard).unwrap().map(|v| v.unwrap().to_integer(guard).unwrap()).sum();
+        assert_eq!(sum, 6);
+
+        let s = script::eval(guard, "'h\u{1F600}'").unwrap();
+        assert_eq!(s.iter(guard).unwrap().count(), 2);
+    });
+}
+
+#[test]
+fn throwing_step_ends_iteration() {
+    with_guard(|guard| {
+        let gen = script::eval(guard, "(function* () { yield 1; throw new Error('stop'); })()").unwrap();
+        let mut it = gen.iter(guard).unwrap();
+        assert_eq!(it.next().unwrap().unwrap().to_integer(guard).unwrap(), 1);
+        assert!(matches!(it.next(), Some(Err(Error::JsException(_)))));
+        assert!(it.next().is_none());
+    });
+}
+
+#[test]
+fn non_iterable() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "({})").unwrap();
+        assert!(matches!(v.iter(guard), Err(Error::Type(_))));
+    });
+}
+
+#[test]
+fn dropping_early_closes_the_iterator() {
+    with_guard(|guard| {
+        script::eval(guard, "var cleaned = 0; function* gen() { try { yield 1; yield 2; } finally { cleaned++; } }").unwrap();
+        let cleaned = || script::eval(guard, "cleaned").unwrap().to_integer(guard).unwrap();
+
+        let gen = script::eval(guard, "gen()").unwrap();
+        let mut it = gen.iter(guard).unwrap();
+        assert_eq!(it.next().unwrap().unwrap().to_integer(guard).unwrap(), 1);
+        drop(it);
+        assert_eq!(cleaned(), 1);
+
+        // Exhausted iterators are not closed again.
+        let gen = script::eval(guard, "gen()").unwrap();
+        assert_eq!(gen.iter(guard).unwrap().count(), 2);
+        assert_eq!(cleaned(), 2);
+
+        // Nor are ones whose step threw.
+        let gen = script::eval(guard, "(function* () { try { throw new Error('x'); } finally { cleaned++; } })()").unwrap();
+        let mut it = gen.iter(guard).unwrap();
+        assert!(matches!(it.next(), Some(Err(Error::JsException(_)))));
+        drop(it);
+        assert_eq!(cleaned(), 3);
+    });
+}
+
+#[test]
+fn throwing_return_is_ignored() {
+    with_guard(|guard| {
+        let gen = script::eval(guard, "(function* () { try { yield 1; yield 2; } finally { throw new Error('cleanup'); } })()").unwrap();
+        let mut it = gen.iter(guard).unwrap();
+        assert!(it.next().unwrap().is_ok());
+        drop(it);
+        assert_eq!(script::eval(guard, "6 * 7").unwrap().to_integer(guard).unwrap(), 42);
+    });
+}
@@ -0,0 +1,98 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::{IntoJs, Value};
+use catswords_jsrt::{json, script, Error};
+use common::with_guard;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn parse_and_stringify() {
+    with_guard(|guard| {
+        let v = json::parse(guard, r#"{"a":[1,2,{"b":null}],"c":"é"}"#).unwrap();
+        assert_eq!(json::stringify(guard, &v, 0).unwrap(), r#"{"a":[1,2,{"b":null}],"c":"é"}"#);
+        assert_eq!(json::stringify(guard, &v, 2).unwrap().lines().count(), 11);
+    });
+}
+
+#[test]
+fn invalid_json_is_a_syntax_error() {
+    with_guard(|guard| {
+        for text in ["{", "{'a': 1}", "[1,]", ""] {
+            let Err(Error::JsException(e)) = json::parse(guard, text) else { panic!("{:?} parsed", text) };
+            assert_eq!(e.name.as_deref(), Some("SyntaxError"), "{:?}", text);
+        }
+        // The context stays usable afterwards.
+        assert_eq!(script::eval(guard, "1 + 1").unwrap().to_integer(guard).unwrap(), 2);
+    });
+}
+
+#[test]
+fn cyclic_values_are_type_errors() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "const o = { a: {} }; o.a.o = o; o").unwrap();
+        let Err(Error::JsException(e)) = json::stringify(guard, &v, 0) else { panic!("cycle stringified") };
+        assert_eq!(e.name.as_deref(), Some("TypeError"));
+
+        let f = script::eval(guard, "(function () {})").unwrap();
+        assert!(matches!(json::stringify(guard, &f, 0), Err(Error::Type(_))));
+        assert!(matches!(json::stringify(guard, &Value::undefined(guard).unwrap(), 0), Err(Error::Type(_))));
+    });
+}
+
+#[test]
+fn reviver_and_replacer() {
+    with_guard(|guard| {
+        let v = json::parse_with_reviver(guard, r#"{"a":1,"b":2}"#, |guard, key, value| {
+            if key == "b" {
+                Value::undefined(guard)
+            } else {
+                Ok(value)
+            }
+        })
+        .unwrap();
+        assert_eq!(json::stringify(guard, &v, 0).unwrap(), r#"{"a":1}"#);
+
+        let out = json::stringify_with_replacer(guard, &v, 0, |guard, key, value| {
+            if key == "a" {
+                "replaced".into_js(guard)
+            } else {
+                Ok(value)
+            }
+        })
+        .unwrap();
+        assert_eq!(out, r#"{"a":"replaced"}"#);
+
+        let err = json::parse_with_reviver(guard, "[1]", |_, _, _| Err(catswords_jsrt::type_err("no".to_string())));
+        assert!(err.is_err());
+    });
+}
+
+#[test]
+fn callbacks_are_freed_on_collection() {
+    struct Dropped(Arc<AtomicUsize>);
+
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    with_guard(|guard| {
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let v = json::parse(guard, "[1, 2, 3]").unwrap();
+        for _ in 0..1000 {
+            let token = Dropped(dropped.clone());
+            json::stringify_with_replacer(guard, &v, 0, move |_, _, value| {
+                let _ = &token;
+                Ok(value)
+            })
+            .unwrap();
+        }
+        guard.runtime().collect_garbage().unwrap();
+        // The collector is conservative, so a few may survive.
+        assert!(dropped.load(Ordering::SeqCst) > 900, "{}", dropped.load(Ordering::SeqCst));
+    });
+}
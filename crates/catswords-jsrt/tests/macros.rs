@@ -0,0 +1,169 @@
+#![cfg(all(jsrt_engine_tests, feature = "macros"))]
+
+mod common;
+
+use catswords_jsrt::value::{FromJs, IntoJs, Rest};
+use catswords_jsrt::{js_class, js_function, js_methods, script, type_err, Context, Guard, Result, Runtime};
+use common::with_guard;
+
+fn eval_string(guard: &Guard<'_>, code: &str) -> String {
+    script::eval(guard, code).unwrap().to_rust_string(guard).unwrap()
+}
+
+#[derive(Debug, PartialEq, FromJs, IntoJs)]
+struct Point {
+    x: f64,
+    #[js(name = "Y")]
+    y: f64,
+}
+
+#[derive(Debug, PartialEq, FromJs, IntoJs)]
+enum Shape {
+    Empty,
+    #[js(name = "dot")]
+    Dot,
+    Circle { center: Point, radius: f64 },
+    Square(f64),
+}
+
+#[test]
+fn derived_types_round_trip() {
+    with_guard(|guard| {
+        let point = Point { x: 1.0, y: 2.0 }.into_js(guard).unwrap();
+        guard.context().set_global("point", &point).unwrap();
+        assert_eq!(eval_string(guard, "JSON.stringify(point)"), r#"{"x":1,"Y":2}"#);
+        let back = Point::from_js(guard, &script::eval(guard, "({ x: 3, Y: 4 })").unwrap()).unwrap();
+        assert_eq!(back, Point { x: 3.0, y: 4.0 });
+
+        for shape in [
+            Shape::Empty,
+            Shape::Dot,
+            Shape::Circle { center: Point { x: 0.0, y: 1.0 }, radius: 2.0 },
+            Shape::Square(3.0),
+        ] {
+            let json = {
+                let v = shape.into_js(guard).unwrap();
+                guard.context().set_global("shape", &v).unwrap();
+                eval_string(guard, "JSON.stringify(shape)")
+            };
+            let back = Shape::from_js(guard, &script::eval(guard, &format!("({})", json)).unwrap()).unwrap();
+            match &back {
+                Shape::Empty => assert_eq!(json, r#""Empty""#),
+                Shape::Dot => assert_eq!(json, r#""dot""#),
+                Shape::Circle { .. } => assert_eq!(json, r#"{"Circle":{"center":{"x":0,"Y":1},"radius":2}}"#),
+                Shape::Square(_) => assert_eq!(json, r#"{"Square":3}"#),
+            }
+        }
+
+        let err = Shape::from_js(guard, &script::eval(guard, "'Triangle'").unwrap()).unwrap_err();
+        assert!(err.to_string().contains("unknown Shape variant"), "{}", err);
+        let err = Point::from_js(guard, &script::eval(guard, "({ x: 'a', Y: 1 })").unwrap()).unwrap_err();
+        assert!(err.to_string().contains("property \"x\""), "{}", err);
+    });
+}
+
+#[js_function]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[js_function(name = "sumAll")]
+fn sum(first: f64, rest: Rest<f64>) -> f64 {
+    first + rest.0.iter().sum::<f64>()
+}
+
+#[js_function]
+fn checked_div(a: i32, b: i32) -> Result<i32> {
+    a.checked_div(b).ok_or_else(|| type_err("division by zero".to_string()))
+}
+
+#[test]
+fn functions_are_callable_from_script() {
+    let runtime = Runtime::new().unwrap();
+    let context = Context::new(&runtime).unwrap();
+    add::register(&context).unwrap();
+    sum::register(&context).unwrap();
+    let guard = context.make_current().unwrap();
+    let div = checked_div::function(&guard).unwrap();
+    context.set_global("checkedDiv", div.as_value()).unwrap();
+
+    assert_eq!(script::eval(&guard, "add(2, 3)").unwrap().to_integer(&guard).unwrap(), 5);
+    assert_eq!(script::eval(&guard, "sumAll(1, 2, 3.5)").unwrap().to_f64(&guard).unwrap(), 6.5);
+    assert_eq!(script::eval(&guard, "typeof sum").unwrap().to_rust_string(&guard).unwrap(), "undefined");
+    assert_eq!(script::eval(&guard, "checkedDiv(7, 2)").unwrap().to_integer(&guard).unwrap(), 3);
+    let msg = eval_string(&guard, "try { checkedDiv(1, 0) } catch (e) { e.name + ': ' + e.message }");
+    assert_eq!(msg, "TypeError: division by zero");
+    let msg = eval_string(&guard, "try { add(1) } catch (e) { e.name }");
+    assert_eq!(msg, "TypeError");
+}
+
+#[js_class]
+struct Counter {
+    n: i64,
+}
+
+#[js_methods]
+impl Counter {
+    #[js(constructor)]
+    fn new(start: Option<i64>) -> Self {
+        Counter { n: start.unwrap_or(0) }
+    }
+
+    fn increment(&mut self) -> i64 {
+        self.n += 1;
+        self.n
+    }
+
+    #[js(name = "addTo")]
+    fn add_to(&self, other: i64) -> i64 {
+        self.n + other
+    }
+
+    #[js(get)]
+    fn value(&self) -> i64 {
+        self.n
+    }
+
+    #[js(set)]
+    fn set_value(&mut self, n: i64) -> Result<()> {
+        if n < 0 {
+            return Err(type_err("negative count".to_string()));
+        }
+        self.n = n;
+        Ok(())
+    }
+
+    fn zero() -> i64 {
+        0
+    }
+}
+
+#[js_class(name = "Token")]
+struct Opaque;
+
+#[js_methods]
+impl Opaque {
+    fn kind(&self) -> String {
+        "opaque".to_string()
+    }
+}
+
+#[test]
+fn classes_are_usable_from_script() {
+    let runtime = Runtime::new().unwrap();
+    let context = Context::new(&runtime).unwrap();
+    context.register_class::<Counter>().unwrap();
+    context.register_class::<Opaque>().unwrap();
+    let guard = context.make_current().unwrap();
+
+    let v = script::eval(&guard, "const c = new Counter(5); c.increment(); c.value = c.value + 10; c").unwrap();
+    assert_eq!(v.downcast_ref::<Counter>(&guard).unwrap().n, 16);
+    assert_eq!(eval_string(&guard, "[new Counter().value, c.addTo(4), Counter.zero()].join()"), "0,20,0");
+    assert_eq!(eval_string(&guard, "try { c.value = -1 } catch (e) { e.message }"), "negative count");
+    assert_eq!(eval_string(&guard, "try { Counter(1) } catch (e) { e.name }"), "TypeError");
+    assert_eq!(eval_string(&guard, "Object.keys(c).length + ''"), "0");
+
+    assert_eq!(eval_string(&guard, "typeof Token"), "function");
+    let msg = eval_string(&guard, "try { new Token(); 'constructed' } catch (e) { e.message }");
+    assert_eq!(msg, "Token cannot be constructed from script");
+}
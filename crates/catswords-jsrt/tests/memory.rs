@@ -0,0 +1,123 @@
+#![cfg(jsrt_engine_tests)]
+
+use catswords_jsrt::{script, Context, Error, JsErrorCode, MemoryEvent, Runtime};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const ALLOCATE: &str = "
+    var chunks = [];
+    for (var i = 0; i < 200; i++) chunks.push(new Array(100000).fill(i));
+    chunks.length
+";
+
+fn out_of_memory(err: &Error) -> bool {
+    match err {
+        Error::OutOfMemory => true,
+        Error::JsException(e) => e.message.to_lowercase().contains("memory"),
+        _ => false,
+    }
+}
+
+#[test]
+fn memory_limit_fails_allocations_until_raised() {
+    let runtime = Runtime::builder().disable_fatal_on_oom(true).build().unwrap();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    let limit = runtime.memory_usage().unwrap() + 4 * 1024 * 1024;
+    runtime.set_memory_limit(Some(limit)).unwrap();
+    assert_eq!(runtime.memory_limit().unwrap(), Some(limit));
+
+    let err = script::eval(&guard, ALLOCATE).unwrap_err();
+    assert!(out_of_memory(&err), "{:?}", err);
+
+    runtime.set_memory_limit(None).unwrap();
+    assert_eq!(runtime.memory_limit().unwrap(), None);
+    assert_eq!(script::eval(&guard, "chunks = null; 6 * 7").unwrap().to_integer(&guard).unwrap(), 42);
+    assert_eq!(script::eval(&guard, ALLOCATE).unwrap().to_integer(&guard).unwrap(), 200);
+}
+
+#[test]
+fn builder_memory_limit() {
+    let runtime = Runtime::builder().disable_fatal_on_oom(true).memory_limit(64 * 1024 * 1024).build().unwrap();
+    assert_eq!(runtime.memory_limit().unwrap(), Some(64 * 1024 * 1024));
+}
+
+#[test]
+fn allocation_callback_can_veto() {
+    let runtime = Runtime::builder().disable_fatal_on_oom(true).build().unwrap();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+
+    let veto = Arc::new(AtomicBool::new(true));
+    let allocations = Arc::new(AtomicUsize::new(0));
+    {
+        let veto = veto.clone();
+        let allocations = allocations.clone();
+        runtime
+            .set_memory_allocation_callback(Some(move |event, _size| {
+                if event == MemoryEvent::Allocate {
+                    allocations.fetch_add(1, Ordering::SeqCst);
+                    return !veto.load(Ordering::SeqCst);
+                }
+                true
+            }))
+            .unwrap();
+    }
+
+    let err = script::eval(&guard, ALLOCATE).unwrap_err();
+    assert!(out_of_memory(&err), "{:?}", err);
+    assert!(allocations.load(Ordering::SeqCst) > 0);
+
+    veto.store(false, Ordering::SeqCst);
+    assert_eq!(script::eval(&guard, ALLOCATE).unwrap().to_integer(&guard).unwrap(), 200);
+
+    runtime.set_memory_allocation_callback(None::<fn(MemoryEvent, usize) -> bool>).unwrap();
+}
+
+#[test]
+fn before_collect_callback_runs_until_replaced_or_cleared() {
+    let runtime = Runtime::new().unwrap();
+    let first = Arc::new(AtomicUsize::new(0));
+    let second = Arc::new(AtomicUsize::new(0));
+
+    let counter = first.clone();
+    runtime
+        .set_before_collect_callback(Some(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }))
+        .unwrap();
+    runtime.collect_garbage().unwrap();
+    assert!(first.load(Ordering::SeqCst) >= 1);
+
+    let counter = second.clone();
+    runtime
+        .set_before_collect_callback(Some(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }))
+        .unwrap();
+    let before = first.load(Ordering::SeqCst);
+    runtime.collect_garbage().unwrap();
+    assert_eq!(first.load(Ordering::SeqCst), before);
+    assert!(second.load(Ordering::SeqCst) >= 1);
+
+    runtime.set_before_collect_callback(None::<fn()>).unwrap();
+    let before = second.load(Ordering::SeqCst);
+    runtime.collect_garbage().unwrap();
+    assert_eq!(second.load(Ordering::SeqCst), before);
+}
+
+#[test]
+fn idle_needs_idle_processing() {
+    let runtime = Runtime::new().unwrap();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    let err = runtime.idle(&guard).unwrap_err();
+    assert_eq!(err.code(), JsErrorCode::JsErrorIdleNotEnabled);
+    drop(guard);
+
+    let runtime = Runtime::builder().enable_idle_processing(true).build().unwrap();
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    runtime.idle(&guard).unwrap();
+}
@@ -0,0 +1,214 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::module::{self, ModuleLoader};
+use catswords_jsrt::value::PersistentValue;
+use catswords_jsrt::{err_msg, script, Context, Error, Guard, JsErrorCode, Result, Runtime};
+use common::with_guard;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// An in-memory loader the test can still change after handing it over.
+#[derive(Clone, Default)]
+struct Shared(Arc<Mutex<HashMap<String, String>>>);
+
+impl Shared {
+    fn with(modules: &[(&str, &str)]) -> Self {
+        let shared = Self::default();
+        for (name, source) in modules {
+            shared.set(name, source);
+        }
+        shared
+    }
+
+    fn set(&self, name: &str, source: &str) {
+        self.0.lock().unwrap().insert(name.to_string(), source.to_string());
+    }
+}
+
+impl ModuleLoader for Shared {
+    fn resolve(&self, specifier: &str, _referrer: Option<&str>) -> Result<String> {
+        if specifier.starts_with("bad:") {
+            return Err(err_msg(JsErrorCode::JsErrorInvalidArgument, format!("cannot resolve {}", specifier)));
+        }
+        Ok(specifier.to_string())
+    }
+
+    fn load(&self, name: &str) -> Result<String> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| err_msg(JsErrorCode::JsErrorInvalidArgument, format!("no module {}", name)))
+    }
+}
+
+fn get_i32(guard: &Guard<'_>, namespace: &catswords_jsrt::value::Object<'_>, name: &str) -> i32 {
+    namespace.get(guard, name).unwrap().to_integer(guard).unwrap()
+}
+
+#[test]
+fn imports_and_caching() {
+    with_guard(|guard| {
+        let loader = Shared::with(&[
+            ("main", "import { two } from 'lib'; export const four = two * 2;"),
+            ("lib", "globalThis.loads = (globalThis.loads || 0) + 1; export const two = 2;"),
+        ]);
+        guard.context().set_module_loader(loader).unwrap();
+        let main = module::import(guard, "main").unwrap();
+        assert_eq!(get_i32(guard, &main, "four"), 4);
+        let lib = module::import(guard, "lib").unwrap();
+        assert_eq!(get_i32(guard, &lib, "two"), 2);
+        assert_eq!(script::eval(guard, "loads").unwrap().to_integer(guard).unwrap(), 1);
+    });
+}
+
+#[test]
+fn cycles() {
+    with_guard(|guard| {
+        let loader = Shared::with(&[
+            ("a", "import { b } from 'b'; export const a = 1; export function sum() { return a + b; }"),
+            ("b", "import { a, sum } from 'a'; export const b = 2; export function viaA() { return sum(); }"),
+        ]);
+        guard.context().set_module_loader(loader).unwrap();
+        let a = module::import(guard, "a").unwrap();
+        let sum = a.get(guard, "sum").unwrap().as_function(guard).unwrap();
+        assert_eq!(sum.call(guard, &[]).unwrap().to_integer(guard).unwrap(), 3);
+        let b = module::import(guard, "b").unwrap();
+        let via_a = b.get(guard, "viaA").unwrap().as_function(guard).unwrap();
+        assert_eq!(via_a.call(guard, &[]).unwrap().to_integer(guard).unwrap(), 3);
+    });
+}
+
+#[test]
+fn loader_errors() {
+    with_guard(|guard| {
+        assert!(module::import(guard, "anything").is_err());
+
+        let loader = Shared::with(&[
+            ("missing-dep", "import 'nowhere'; export const x = 1;"),
+            ("unresolved-dep", "import 'bad:dep'; export const x = 1;"),
+            ("syntax", "export const = ;"),
+            ("throws", "throw new RangeError('at load');"),
+        ]);
+        guard.context().set_module_loader(loader).unwrap();
+
+        let err = module::import(guard, "missing-dep").err().unwrap();
+        assert!(err.to_string().contains("no module nowhere"), "{}", err);
+        let err = module::import(guard, "unresolved-dep").err().unwrap();
+        assert!(err.to_string().contains("cannot resolve bad:dep"), "{}", err);
+        assert!(matches!(module::import(guard, "bad:root"), Err(Error::Jsrt { .. })));
+
+        let Err(Error::JsException(e)) = module::import(guard, "syntax") else { panic!("syntax error expected") };
+        assert_eq!(e.name.as_deref(), Some("SyntaxError"));
+        let Err(Error::JsException(e)) = module::import(guard, "throws") else { panic!("exception expected") };
+        assert_eq!(e.name.as_deref(), Some("RangeError"));
+    });
+}
+
+#[test]
+fn failed_dependencies_are_retried() {
+    with_guard(|guard| {
+        let loader = Shared::with(&[
+            ("first", "import { v } from 'dep'; export const w = v;"),
+            ("second", "import { v } from 'dep'; export const w = v + 1;"),
+            ("broken", "import { v } from 'bad-syntax'; export const w = v;"),
+            ("bad-syntax", "export const v = ;"),
+        ]);
+        guard.context().set_module_loader(loader.clone()).unwrap();
+
+        assert!(module::import(guard, "first").is_err());
+        loader.set("dep", "export const v = 10;");
+        let second = module::import(guard, "second").unwrap();
+        assert_eq!(get_i32(guard, &second, "w"), 11);
+
+        assert!(module::import(guard, "broken").is_err());
+        loader.set("bad-syntax", "export const v = 5;");
+        loader.set("fixed", "import { v } from 'bad-syntax'; export const w = v;");
+        let fixed = module::import(guard, "fixed").unwrap();
+        assert_eq!(get_i32(guard, &fixed, "w"), 5);
+    });
+}
+
+#[test]
+fn eval_module_and_dynamic_import() {
+    with_guard(|guard| {
+        guard.context().set_module_loader(Shared::with(&[("dep", "export default 7;")])).unwrap();
+        let m = module::eval_module(guard, "inline", "import d from 'dep'; export const x = d;").unwrap();
+        assert_eq!(get_i32(guard, &m, "x"), 7);
+        assert!(module::eval_module(guard, "inline", "export {}").is_err());
+
+        script::eval(guard, "import('dep').then(m => { globalThis.got = m.default; })").unwrap();
+        module::load_pending(guard).unwrap();
+        assert_eq!(script::eval(guard, "got").unwrap().to_integer(guard).unwrap(), 7);
+    });
+}
+
+#[test]
+fn imports_after_the_runtime_moved() {
+    let runtime = Runtime::new().unwrap();
+    let loader = Shared::with(&[("dep", "export const v = 3;")]);
+    let start = {
+        let context = Context::new(&runtime).unwrap();
+        context.set_module_loader(loader).unwrap();
+        let guard = context.make_current().unwrap();
+        let f = script::eval(&guard, "(function () { return import('dep'); })").unwrap();
+        PersistentValue::new(&guard, &f).unwrap()
+    };
+
+    // The function's context keeps its module state, which must still find the
+    // runtime when script in that context imports after the move.
+    let runtime = Box::new(runtime);
+    let context = Context::new(&runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    let start = start.get(&guard).unwrap().as_function(&guard).unwrap();
+    start.call(&guard, &[]).unwrap();
+}
+
+// Panics for specifiers starting with "panic:" in `resolve`, and for modules
+// named "boom" in `load`.
+struct Panicky(Shared);
+
+impl ModuleLoader for Panicky {
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String> {
+        if specifier.starts_with("panic:") {
+            panic!("resolve {}", specifier);
+        }
+        self.0.resolve(specifier, referrer)
+    }
+
+    fn load(&self, name: &str) -> Result<String> {
+        if name == "boom" {
+            panic!("load {}", name);
+        }
+        self.0.load(name)
+    }
+}
+
+#[test]
+fn failed_dynamic_imports_reject() {
+    with_guard(|guard| {
+        let loader = Panicky(Shared::with(&[("ok", "export default 1;"), ("uses-boom", "import 'boom';")]));
+        guard.context().set_module_loader(loader).unwrap();
+        script::eval(guard, "globalThis.results = {}; function settle(name) { import(name).then(() => { results[name] = 'resolved'; }, e => { results[name] = 'rejected: ' + e.message; }); }").unwrap();
+        for name in ["ok", "missing", "bad:spec", "panic:spec", "boom", "uses-boom"] {
+            script::eval(guard, &format!("settle({:?})", name)).unwrap();
+            module::load_pending(guard).unwrap();
+        }
+        let result = |name: &str| {
+            script::eval(guard, &format!("String(results[{:?}])", name)).unwrap().to_rust_string(guard).unwrap()
+        };
+        assert_eq!(result("ok"), "resolved");
+        assert!(result("missing").contains("no module missing"), "{}", result("missing"));
+        assert!(result("bad:spec").contains("cannot resolve bad:spec"), "{}", result("bad:spec"));
+        assert!(result("panic:spec").contains("resolve panic:spec"), "{}", result("panic:spec"));
+        assert!(result("boom").contains("load boom"), "{}", result("boom"));
+        assert!(result("uses-boom").starts_with("rejected"), "{}", result("uses-boom"));
+
+        // Static imports of a panicking dependency fail as well.
+        assert!(module::import(guard, "uses-boom").is_err());
+        assert!(module::import(guard, "ok").is_ok());
+    });
+}
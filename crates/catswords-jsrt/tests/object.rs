@@ -0,0 +1,130 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::{Number, Object, PropertyDescriptor, PropertyId, Symbol, Value};
+use catswords_jsrt::{script, Guard};
+use common::with_guard;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+fn eval_string(guard: &Guard<'_>, code: &str) -> String {
+    script::eval(guard, code).unwrap().to_rust_string(guard).unwrap()
+}
+
+#[test]
+fn define_property_with_getter_and_setter() {
+    with_guard(|guard| {
+        let stored = Arc::new(AtomicI32::new(1));
+        let object = Object::new(guard).unwrap();
+        let (get, set) = (stored.clone(), stored.clone());
+        let descriptor = PropertyDescriptor::new()
+            .getter_fn(guard, move |guard, _this| Ok(Number::new(guard, get.load(Ordering::SeqCst)).into()))
+            .setter_fn(guard, move |guard, _this, value| {
+                set.store(value.to_integer(guard)?, Ordering::SeqCst);
+                Ok(())
+            })
+            .enumerable(true);
+        assert!(object.define_property(guard, "x", &descriptor).unwrap());
+        guard.context().set_global("o", object.as_value()).unwrap();
+
+        assert_eq!(script::eval(guard, "o.x").unwrap().to_integer(guard).unwrap(), 1);
+        script::eval(guard, "o.x = 41").unwrap();
+        assert_eq!(stored.load(Ordering::SeqCst), 41);
+        assert_eq!(object.get(guard, "x").unwrap().to_integer(guard).unwrap(), 41);
+        assert_eq!(eval_string(guard, "Object.keys(o).join()"), "x");
+        // Flags left unset default to false.
+        assert_eq!(eval_string(guard, "String(Object.getOwnPropertyDescriptor(o, 'x').configurable)"), "false");
+    });
+}
+
+#[test]
+fn define_property_flags() {
+    with_guard(|guard| {
+        let object = Object::new(guard).unwrap();
+        let descriptor = PropertyDescriptor::new().value(Number::new(guard, 7).into());
+        assert!(object.define_property(guard, "fixed", &descriptor).unwrap());
+        guard.context().set_global("o", object.as_value()).unwrap();
+
+        assert_eq!(eval_string(guard, "'use strict'; try { o.fixed = 8; 'assigned' } catch (e) { e.name }"), "TypeError");
+        assert_eq!(object.get(guard, "fixed").unwrap().to_integer(guard).unwrap(), 7);
+        assert_eq!(eval_string(guard, "Object.keys(o).length + ''"), "0");
+        // Redefining a non-configurable property is refused (false or a TypeError).
+        let descriptor = PropertyDescriptor::new().value(Number::new(guard, 9).into());
+        assert!(!matches!(object.define_property(guard, "fixed", &descriptor), Ok(true)));
+    });
+}
+
+#[test]
+fn delete_reports_non_configurable_properties() {
+    with_guard(|guard| {
+        let object = script::eval(guard, "({ a: 1 })").unwrap().as_object(guard).unwrap();
+        assert!(object.has_own(guard, "a").unwrap());
+        assert!(object.delete(guard, "a").unwrap());
+        assert!(!object.has_own(guard, "a").unwrap());
+        // Deleting a missing property succeeds.
+        assert!(object.delete(guard, "a").unwrap());
+
+        let descriptor = PropertyDescriptor::new().value(Number::new(guard, 1).into()).configurable(false);
+        object.define_property(guard, "b", &descriptor).unwrap();
+        assert!(!object.delete(guard, "b").unwrap());
+        assert!(object.has_own(guard, "b").unwrap());
+    });
+}
+
+#[test]
+fn freeze_and_prevent_extensions() {
+    with_guard(|guard| {
+        let object = script::eval(guard, "({ a: 1 })").unwrap().as_object(guard).unwrap();
+        assert!(object.is_extensible(guard).unwrap());
+        object.freeze(guard).unwrap();
+        assert!(!object.is_extensible(guard).unwrap());
+        guard.context().set_global("o", object.as_value()).unwrap();
+        assert_eq!(eval_string(guard, "String(Object.isFrozen(o))"), "true");
+        assert_eq!(eval_string(guard, "'use strict'; try { o.a = 2; 'assigned' } catch (e) { e.name }"), "TypeError");
+        assert!(!object.delete(guard, "a").unwrap());
+
+        let object = Object::new(guard).unwrap();
+        object.prevent_extensions(guard).unwrap();
+        assert!(!object.is_extensible(guard).unwrap());
+        // `set` uses strict-mode rules, so adding a property throws.
+        assert!(object.set(guard, "late", &Number::new(guard, 1).into()).is_err());
+        assert!(!object.has(guard, "late").unwrap());
+    });
+}
+
+#[test]
+fn symbol_keys() {
+    with_guard(|guard| {
+        let object = Object::new(guard).unwrap();
+        let symbol = Symbol::new(guard, Some("tag")).unwrap();
+        object.set(guard, &symbol, &Value::string_utf8(guard, "by symbol").unwrap()).unwrap();
+        object.set(guard, "tag", &Value::string_utf8(guard, "by name").unwrap()).unwrap();
+
+        assert_eq!(object.get(guard, &symbol).unwrap().to_rust_string(guard).unwrap(), "by symbol");
+        assert_eq!(object.get(guard, "tag").unwrap().to_rust_string(guard).unwrap(), "by name");
+        let pid = PropertyId::from_symbol(guard, &symbol).unwrap();
+        assert!(object.has_own(guard, &pid).unwrap());
+
+        let symbols = object.get_own_property_symbols(guard).unwrap();
+        assert_eq!(symbols.len(guard).unwrap(), 1);
+        let names = object.get_own_property_names(guard).unwrap();
+        assert_eq!(names.len(guard).unwrap(), 1);
+        assert_eq!(names.get(guard, 0).unwrap().to_rust_string(guard).unwrap(), "tag");
+
+        assert!(object.delete(guard, &symbol).unwrap());
+        assert!(!object.has(guard, &symbol).unwrap());
+        assert!(object.has(guard, "tag").unwrap());
+    });
+}
+
+#[test]
+fn cached_property_ids() {
+    with_guard(|guard| {
+        let object = script::eval(guard, "({ n: 3 })").unwrap().as_object(guard).unwrap();
+        let n = PropertyId::new(guard, "n").unwrap();
+        assert_eq!(object.get(guard, &n).unwrap().to_integer(guard).unwrap(), 3);
+        object.set(guard, &n, &Number::new(guard, 4).into()).unwrap();
+        assert_eq!(object.get(guard, "n").unwrap().to_integer(guard).unwrap(), 4);
+    });
+}
@@ -0,0 +1,73 @@
+#![cfg(jsrt_engine_tests)]
+
+use catswords_jsrt::value::Function;
+use catswords_jsrt::{script, Context, PanicPolicy, Result, Runtime};
+use std::process::Command;
+
+// Runs `source` in `runtime` with a global `boom` that panics with "boom" and
+// returns the string it evaluates to.
+fn caught(runtime: &Runtime, source: &str) -> String {
+    let context = Context::new(runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    let boom = Function::wrap(&guard, || -> Result<()> { panic!("boom") });
+    context.set_global("boom", boom.as_value()).unwrap();
+    script::eval(&guard, source).unwrap().to_rust_string(&guard).unwrap()
+}
+
+#[test]
+fn default_policy_throws() {
+    let runtime = Runtime::new().unwrap();
+    assert_eq!(runtime.panic_policy(), PanicPolicy::Throw);
+    let msg = caught(&runtime, "try { boom() } catch (e) { e instanceof Error && e.message }");
+    assert_eq!(msg, "native callback panicked: boom");
+}
+
+#[test]
+fn log_and_throw_still_throws() {
+    let runtime = Runtime::builder().panic_policy(PanicPolicy::LogAndThrow).build().unwrap();
+    assert_eq!(runtime.panic_policy(), PanicPolicy::LogAndThrow);
+    let msg = caught(&runtime, "try { boom() } catch (e) { e.message }");
+    assert_eq!(msg, "native callback panicked: boom");
+}
+
+#[test]
+fn policy_setter() {
+    let runtime = Runtime::new().unwrap();
+    runtime.set_panic_policy(PanicPolicy::LogAndThrow);
+    assert_eq!(runtime.panic_policy(), PanicPolicy::LogAndThrow);
+    runtime.set_panic_policy(PanicPolicy::Throw);
+    assert_eq!(runtime.panic_policy(), PanicPolicy::Throw);
+    let msg = caught(&runtime, "try { boom() } catch (e) { e.message }");
+    assert_eq!(msg, "native callback panicked: boom");
+}
+
+#[test]
+fn panic_in_a_constructor_call() {
+    let runtime = Runtime::new().unwrap();
+    let msg = caught(&runtime, "try { new boom(); 'constructed' } catch (e) { e.message }");
+    assert_eq!(msg, "native callback panicked: boom");
+}
+
+// Abort ends the process, so the test runs itself again as a child that
+// triggers the panic, and checks that the child died.
+#[test]
+fn abort_policy_aborts() {
+    if std::env::var_os("JSRT_ABORT_CHILD").is_some() {
+        let runtime = Runtime::builder().panic_policy(PanicPolicy::Abort).build().unwrap();
+        caught(&runtime, "try { boom() } catch (e) { 'survived' }");
+        return;
+    }
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["abort_policy_aborts", "--exact", "--nocapture", "--test-threads=1"])
+        .env("JSRT_ABORT_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(output.status.signal(), Some(6));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("catswords-jsrt: native callback panicked: boom"), "{}", stderr);
+}
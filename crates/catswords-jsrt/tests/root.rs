@@ -0,0 +1,79 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::Class;
+use catswords_jsrt::{script, Context, Guard, JsErrorCode, RootStore, RootedValue, Runtime};
+use common::with_guard;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Tracker(Arc<AtomicUsize>);
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+// Kept out of line so no stack slot of the caller still points at the object.
+#[inline(never)]
+fn root_tracker(guard: &Guard<'_>, store: &mut RootStore, drops: &Arc<AtomicUsize>) -> RootedValue {
+    let drops = drops.clone();
+    let class = Class::<Tracker>::new(guard, "Tracker", move |_, _| Ok(Tracker(drops.clone()))).unwrap().build().unwrap();
+    guard.context().set_global("Tracker", class.as_value()).unwrap();
+    let rooted = store.root(guard, script::eval(guard, "new Tracker()").unwrap()).unwrap();
+    script::eval(guard, "Tracker = undefined").unwrap();
+    rooted
+}
+
+#[test]
+fn rooted_counts_live_values() {
+    with_guard(|guard| {
+        let mut store = RootStore::new(guard).unwrap();
+        let a = store.root(guard, script::eval(guard, "({ a: 1 })").unwrap()).unwrap();
+        let b = store.root(guard, script::eval(guard, "[2]").unwrap()).unwrap();
+        assert_eq!(store.rooted(), 2);
+
+        drop(a);
+        assert_eq!(store.rooted(), 1);
+
+        guard.runtime().collect_garbage().unwrap();
+        let b = b.get(guard).unwrap().as_array(guard).unwrap();
+        assert_eq!(b.get(guard, 0).unwrap().to_integer(guard).unwrap(), 2);
+    });
+}
+
+#[test]
+fn rooted_value_survives_collection_until_dropped() {
+    with_guard(|guard| {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut store = RootStore::new(guard).unwrap();
+        let rooted = root_tracker(guard, &mut store, &drops);
+
+        guard.runtime().collect_garbage().unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert!(rooted.get(guard).unwrap().downcast_ref::<Tracker>(guard).is_ok());
+
+        drop(rooted);
+        assert_eq!(store.rooted(), 0);
+        guard.runtime().collect_garbage().unwrap();
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[test]
+fn root_rejects_values_of_another_runtime() {
+    let runtime = Runtime::new().unwrap();
+    let mut store = {
+        let context = Context::new(&runtime).unwrap();
+        let guard = context.make_current().unwrap();
+        RootStore::new(&guard).unwrap()
+    };
+    with_guard(|guard| {
+        match store.root(guard, script::eval(guard, "({})").unwrap()) {
+            Err(err) => assert_eq!(err.code(), JsErrorCode::JsErrorWrongRuntime),
+            Ok(_) => panic!("rooted a value of another runtime"),
+        }
+    });
+}
@@ -0,0 +1,59 @@
+#![cfg(jsrt_engine_tests)]
+
+use catswords_jsrt::{script, Context, JsRuntimeAttributes, Runtime};
+
+fn run(runtime: &Runtime, source: &str) -> String {
+    let context = Context::new(runtime).unwrap();
+    let guard = context.make_current().unwrap();
+    script::eval(&guard, source).unwrap().to_rust_string(&guard).unwrap()
+}
+
+const TRY_EVAL: &str = "
+    var results = [];
+    try { results.push(eval('1 + 1')); } catch (e) { results.push('eval blocked'); }
+    try { results.push(Function('return 3')()); } catch (e) { results.push('Function blocked'); }
+    results.join(',')
+";
+
+#[test]
+fn eval_is_allowed_by_default() {
+    let runtime = Runtime::new().unwrap();
+    assert_eq!(runtime.attributes(), JsRuntimeAttributes::JsRuntimeAttributeNone);
+    assert_eq!(run(&runtime, TRY_EVAL), "2,3");
+}
+
+#[test]
+fn disable_eval_blocks_eval_and_function() {
+    let runtime = Runtime::builder().disable_eval(true).build().unwrap();
+    assert!(runtime.attributes().contains(JsRuntimeAttributes::JsRuntimeAttributeDisableEval));
+    assert_eq!(run(&runtime, TRY_EVAL), "eval blocked,Function blocked");
+}
+
+#[test]
+fn builder_flags_combine_and_clear() {
+    let runtime = Runtime::builder()
+        .disable_eval(true)
+        .disable_native_code_generation(true)
+        .disable_background_work(true)
+        .disable_eval(false)
+        .build()
+        .unwrap();
+    let attributes = runtime.attributes();
+    assert!(!attributes.contains(JsRuntimeAttributes::JsRuntimeAttributeDisableEval));
+    assert!(attributes.contains(JsRuntimeAttributes::JsRuntimeAttributeDisableNativeCodeGeneration));
+    assert!(attributes.contains(JsRuntimeAttributes::JsRuntimeAttributeDisableBackgroundWork));
+    // Interpreter only, still runs script.
+    assert_eq!(run(&runtime, TRY_EVAL), "2,3");
+}
+
+#[test]
+fn attributes_replace_the_whole_set() {
+    let runtime = Runtime::builder()
+        .disable_eval(true)
+        .attributes(JsRuntimeAttributes::JsRuntimeAttributeAllowScriptInterrupt)
+        .build()
+        .unwrap();
+    assert_eq!(runtime.attributes(), JsRuntimeAttributes::JsRuntimeAttributeAllowScriptInterrupt);
+    assert!(runtime.interrupt_handle().is_ok());
+    assert_eq!(run(&runtime, TRY_EVAL), "2,3");
+}
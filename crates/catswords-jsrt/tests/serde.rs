@@ -0,0 +1,113 @@
+#![cfg(all(jsrt_engine_tests, feature = "serde"))]
+
+mod common;
+
+use ::serde::{Deserialize, Serialize};
+use catswords_jsrt::serde::{from_value, to_value};
+use catswords_jsrt::{script, Error, Guard};
+use common::with_guard;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { w: u32, h: u32 },
+    Pair(i8, i8),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    limits: BTreeMap<String, i64>,
+    shapes: Vec<Shape>,
+    parent: Option<Box<Config>>,
+    unit: (),
+    tuple: (bool, char),
+}
+
+fn sample() -> Config {
+    Config {
+        name: "srv".to_string(),
+        port: 8080,
+        tags: vec!["a".to_string(), "b".to_string()],
+        limits: [("x".to_string(), -3), ("y".to_string(), 1 << 40)].into_iter().collect(),
+        shapes: vec![Shape::Empty, Shape::Circle(0.5), Shape::Rect { w: 2, h: 3 }, Shape::Pair(-1, 1)],
+        parent: Some(Box::new(Config {
+            name: "root".to_string(),
+            port: 1,
+            tags: Vec::new(),
+            limits: BTreeMap::new(),
+            shapes: Vec::new(),
+            parent: None,
+            unit: (),
+            tuple: (false, 'z'),
+        })),
+        unit: (),
+        tuple: (true, '\u{1F600}'),
+    }
+}
+
+fn json(guard: &Guard<'_>, code: &str) -> String {
+    script::eval(guard, code).unwrap().to_rust_string(guard).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    with_guard(|guard| {
+        let config = sample();
+        let value = to_value(guard, &config).unwrap();
+        assert_eq!(from_value::<Config>(guard, &value).unwrap(), config);
+
+        guard.context().set_global("config", &value).unwrap();
+        assert_eq!(json(guard, "JSON.stringify(config.shapes)"), r#"["Empty",{"Circle":0.5},{"Rect":{"w":2,"h":3}},{"Pair":[-1,1]}]"#);
+        assert_eq!(json(guard, "typeof config.unit + ',' + (config.parent.parent === null)"), "undefined,true");
+    });
+}
+
+#[test]
+fn from_script_values() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "({ w: 4, h: 5, extra: 'ignored' })").unwrap();
+        let map = from_value::<BTreeMap<String, ::serde::de::IgnoredAny>>(guard, &v).unwrap();
+        assert_eq!(map.len(), 3);
+
+        let v = script::eval(guard, "({ Rect: { h: 5, w: 4 } })").unwrap();
+        assert_eq!(from_value::<Shape>(guard, &v).unwrap(), Shape::Rect { w: 4, h: 5 });
+    });
+}
+
+#[test]
+fn mismatches_are_type_errors() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "({ w: -1, h: 2 })").unwrap();
+        assert!(matches!(from_value::<BTreeMap<String, u32>>(guard, &v), Err(Error::Type(_))));
+
+        let v = script::eval(guard, "'Hexagon'").unwrap();
+        assert!(matches!(from_value::<Shape>(guard, &v), Err(Error::Type(_))));
+
+        let v = script::eval(guard, "({ port: 1 })").unwrap();
+        assert!(matches!(from_value::<Config>(guard, &v), Err(Error::Type(_))));
+    });
+}
+
+#[test]
+fn bytes_become_uint8_arrays() {
+    with_guard(|guard| {
+        #[derive(Serialize)]
+        struct Blob<'a> {
+            #[serde(with = "bytes")]
+            data: &'a [u8],
+        }
+        mod bytes {
+            pub fn serialize<S: ::serde::Serializer>(v: &[u8], s: S) -> Result<S::Ok, S::Error> {
+                s.serialize_bytes(v)
+            }
+        }
+        let value = to_value(guard, &Blob { data: &[1, 2, 3] }).unwrap();
+        guard.context().set_global("blob", &value).unwrap();
+        assert_eq!(json(guard, "blob.data instanceof Uint8Array ? Array.from(blob.data).join() : 'no'"), "1,2,3");
+    });
+}
@@ -0,0 +1,114 @@
+#![cfg(jsrt_engine_tests)]
+
+mod common;
+
+use catswords_jsrt::value::JsString;
+use catswords_jsrt::{script, Error};
+use common::with_guard;
+
+#[test]
+fn downcasts_match_the_value_type() {
+    with_guard(|guard| {
+        let array = script::eval(guard, "[1, 2]").unwrap();
+        assert!(array.as_array(guard).is_some());
+        assert!(array.as_object(guard).is_some());
+        assert!(array.as_string(guard).is_none());
+        assert!(array.as_function(guard).is_none());
+
+        let string = script::eval(guard, "'abc'").unwrap();
+        assert!(string.as_string(guard).is_some());
+        assert!(string.as_object(guard).is_none());
+        assert!(string.as_array(guard).is_none());
+        assert!(string.as_symbol(guard).is_none());
+
+        let boolean = script::eval(guard, "true").unwrap();
+        assert!(boolean.as_boolean(guard).is_some());
+        assert!(boolean.as_number(guard).is_none());
+
+        let symbol = script::eval(guard, "Symbol('s')").unwrap();
+        assert!(symbol.as_symbol(guard).is_some());
+        assert!(symbol.as_string(guard).is_none());
+        assert!(symbol.as_object(guard).is_none());
+
+        let function = script::eval(guard, "(function () {})").unwrap();
+        assert!(function.as_function(guard).is_some());
+        assert!(function.as_object(guard).is_some());
+        assert!(function.as_array(guard).is_none());
+    });
+}
+
+#[test]
+fn primitives_are_not_objects() {
+    with_guard(|guard| {
+        for source in ["undefined", "null", "1", "'x'", "false", "Symbol()"] {
+            let v = script::eval(guard, source).unwrap();
+            assert!(v.as_object(guard).is_none(), "{} downcast to an object", source);
+        }
+        // Boxed primitives are objects, but not strings or numbers.
+        let boxed = script::eval(guard, "new String('x')").unwrap();
+        assert!(boxed.as_object(guard).is_some());
+        assert!(boxed.as_string(guard).is_none());
+    });
+}
+
+#[test]
+fn strings_with_lone_surrogates() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "'a\\uD800b'").unwrap();
+        assert!(matches!(v.to_rust_string(guard), Err(Error::Type(_))));
+        assert_eq!(v.to_string_lossy(guard).unwrap(), "a\u{FFFD}b");
+        assert_eq!(v.to_utf16(guard).unwrap(), [0x61, 0xD800, 0x62]);
+
+        let s = JsString::from_utf16(guard, &[0xDC00]).unwrap();
+        assert!(matches!(s.to_rust_string(guard), Err(Error::Type(_))));
+        assert_eq!(s.to_string_lossy(guard).unwrap(), "\u{FFFD}");
+
+        // A proper pair converts either way.
+        let v = script::eval(guard, "'\\uD83D\\uDE00'").unwrap();
+        assert_eq!(v.to_rust_string(guard).unwrap(), "\u{1F600}");
+        assert_eq!(v.to_string_lossy(guard).unwrap(), "\u{1F600}");
+    });
+}
+
+#[test]
+fn string_reads_reject_non_strings() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "42").unwrap();
+        assert!(matches!(v.to_rust_string(guard), Err(Error::Type(_))));
+        assert!(matches!(v.to_string_lossy(guard), Err(Error::Type(_))));
+        assert_eq!(v.coerce_to_string(guard).unwrap(), "42");
+    });
+}
+
+#[test]
+fn checked_number_conversions() {
+    with_guard(|guard| {
+        let eval = |source| script::eval(guard, source).unwrap();
+
+        assert_eq!(eval("-7").to_i64(guard).unwrap(), -7);
+        assert_eq!(eval("4294967295").to_u32(guard).unwrap(), u32::MAX);
+        assert_eq!(eval("2 ** 53").to_u64(guard).unwrap(), 1 << 53);
+
+        for source in ["NaN", "Infinity", "-Infinity", "0.5", "2 ** 63"] {
+            assert!(matches!(eval(source).to_i64(guard), Err(Error::Type(_))), "to_i64({})", source);
+        }
+        for source in ["NaN", "1.25", "-1", "2 ** 32"] {
+            assert!(matches!(eval(source).to_u32(guard), Err(Error::Type(_))), "to_u32({})", source);
+        }
+        assert!(eval("NaN").to_f64(guard).unwrap().is_nan());
+    });
+}
+
+#[test]
+fn number_reads_reject_non_numbers() {
+    with_guard(|guard| {
+        let v = script::eval(guard, "'12'").unwrap();
+        assert!(matches!(v.to_f64(guard), Err(Error::Type(_))));
+        assert!(matches!(v.to_i64(guard), Err(Error::Type(_))));
+        assert!(matches!(v.to_u32(guard), Err(Error::Type(_))));
+        assert_eq!(v.coerce_to_f64(guard).unwrap(), 12.0);
+
+        let v = script::eval(guard, "'x'").unwrap();
+        assert!(v.coerce_to_f64(guard).unwrap().is_nan());
+    });
+}
@@ -2,19 +2,10 @@ extern crate catswords_jsrt as js;
 
 type AnyResult<T> = Result<T, Box<dyn std::error::Error>>;
 
-fn make_multiply(guard: &js::Guard) -> js::value::Function {
-    js::value::Function::new(guard, Box::new(|guard, info| {
-        if info.arguments.len() != 2 {
-            return Err(js::err_msg(
-                js::JsErrorCode::JsErrorInvalidArgument,
-                format!("multiply expects 2 arguments, got {}", info.arguments.len()),
-            ));
-        }
-
-        let a = info.arguments[0].to_integer(guard)?;
-        let b = info.arguments[1].to_integer(guard)?;
-        Ok(js::value::Number::new(guard, a * b).into())
-    }))
+fn make_multiply<'g>(guard: &'g js::Guard) -> js::value::Function<'g> {
+    // Arity and argument types are checked by `wrap`; `multiply(1)` or
+    // `multiply("a", 2)` throw a TypeError into script.
+    js::value::Function::wrap(guard, |a: i32, b: i32| -> js::Result<i32> { Ok(a * b) })
 }
 
 fn scenario_direct_call(guard: &js::Guard, multiply: &js::value::Function) -> AnyResult<()> {